@@ -11,6 +11,16 @@ pub struct Claim {
     pub block_height: u32,
 }
 
+/// Logged when the owner takes a claim's funds back.
+pub struct ClaimDisproved {
+    pub claim_id: u64,
+}
+
+/// Logged when the recipient is paid a claim's funds.
+pub struct ClaimFulfilled {
+    pub claim_id: u64,
+}
+
 abi ClaimsContract {
     #[storage(read, write), payable]
     fn initiate_claim(owner: Address, recipient: Address) -> u64;
@@ -6,12 +6,15 @@ use std::context::this_balance;
 use std::auth::msg_sender;
 use std::asset::transfer;
 use std::block::height;
+use std::logging::log;
 use std::storage::storage_vec::*;
 
 use std::hash::Hash;
 
 use claims_contract_abi::ClaimsContract;
 use claims_contract_abi::Claim;
+use claims_contract_abi::ClaimDisproved;
+use claims_contract_abi::ClaimFulfilled;
 
 storage {
     claim_counter: u64 = 0,
@@ -82,6 +85,7 @@ impl ClaimsContract for Contract {
 
         let _ = storage.claims.remove(claim_id);
         transfer(Identity::Address(claim.owner), claim.asset, claim.amount);
+        log(ClaimDisproved { claim_id });
     }
 
     #[storage(read, write)]
@@ -112,6 +116,7 @@ impl ClaimsContract for Contract {
 
         let _ = storage.claims.remove(claim_id);
         transfer(Identity::Address(claim.recipient), claim.asset, claim.amount);
+        log(ClaimFulfilled { claim_id });
     }
 
     #[storage(read)]
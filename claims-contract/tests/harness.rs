@@ -1,43 +1,71 @@
 use fuels::{
     prelude::*,
+    programs::calls::CallHandler,
     types::{errors::transaction::Reason, ContractId},
 };
 
-abigen!(Contract(
-    name = "ClaimsContract",
-    abi = "out/debug/claims-contract-abi.json"
-));
+use test_support::{claims_contract::ClaimsContract, Harness};
 
 async fn get_contract_instance() -> (
     ClaimsContract<WalletUnlocked>,
     ContractId,
     Vec<WalletUnlocked>,
 ) {
-    let mut wallets = launch_custom_provider_and_get_wallets(
-        WalletsConfig::new(
-            Some(3),             /* Three wallets */
-            Some(1),             /* Single coin (UTXO) */
-            Some(1_000_000_000), /* Amount per coin */
-        ),
-        None,
-        None,
-    )
-    .await
-    .unwrap();
-    let wallet = wallets.pop().unwrap();
+    get_contract_instance_with_assets(&[]).await
+}
 
-    let id = Contract::load_from(
-        "./out/debug/claims-contract.bin",
-        LoadConfiguration::default(),
-    )
-    .unwrap()
-    .deploy(&wallet, TxPolicies::default())
-    .await
-    .unwrap();
+/// Three wallets with a single coin of each asset, the last one deploys the contract
+/// and is left out.
+async fn get_contract_instance_with_assets(
+    assets: &[AssetId],
+) -> (
+    ClaimsContract<WalletUnlocked>,
+    ContractId,
+    Vec<WalletUnlocked>,
+) {
+    let harness = assets
+        .iter()
+        .fold(Harness::builder(), |builder, id| builder.asset(*id))
+        .coins(1, 1_000_000_000)
+        .owner(2)
+        .build()
+        .await;
+
+    let mut wallets = harness.wallets.clone();
+    wallets.pop();
 
-    let instance = ClaimsContract::new(id.clone(), wallet);
+    (harness.contract.clone(), harness.contract_id(), wallets)
+}
 
-    (instance, id.into(), wallets)
+/// Claim 10_000 of each asset from `owner` to `recipient`, returning the claim IDs in
+/// the same order.
+async fn initiate_claims(
+    instance: &ClaimsContract<WalletUnlocked>,
+    owner: &WalletUnlocked,
+    recipient: &WalletUnlocked,
+    assets: &[AssetId],
+) -> Vec<u64> {
+    let mut claim_ids = Vec::new();
+    for asset_id in assets {
+        let call_params = CallParameters::default()
+            .with_amount(10_000)
+            .with_asset_id(*asset_id);
+
+        let claim_id = instance
+            .clone()
+            .with_account(owner.clone())
+            .methods()
+            .initiate_claim(owner.address(), recipient.address())
+            .call_params(call_params)
+            .unwrap()
+            .call()
+            .await
+            .unwrap()
+            .value;
+        claim_ids.push(claim_id);
+    }
+
+    claim_ids
 }
 
 #[tokio::test]
@@ -179,12 +207,7 @@ async fn can_fulfill_claim_after_min_height_reached() {
         .unwrap()
         .value;
 
-    owner
-        .provider()
-        .unwrap()
-        .produce_blocks(112, None)
-        .await
-        .expect("should be able to produce blocks");
+    test_support::produce_blocks(owner.provider().unwrap(), 112).await;
 
     instance
         .clone()
@@ -205,3 +228,93 @@ async fn can_fulfill_claim_after_min_height_reached() {
 
     assert_eq!(contract_balance, 0);
 }
+
+#[tokio::test]
+async fn can_disprove_claims_of_several_assets_in_one_call() {
+    let other_asset = AssetId::new([1; 32]);
+    let (instance, _id, mut wallets) =
+        get_contract_instance_with_assets(&[AssetId::zeroed(), other_asset]).await;
+
+    let owner = wallets.pop().unwrap();
+    let recipient = wallets.pop().unwrap();
+
+    let claim_ids = initiate_claims(
+        &instance,
+        &owner,
+        &recipient,
+        &[AssetId::zeroed(), other_asset],
+    )
+    .await;
+
+    let owner_instance = instance.clone().with_account(owner.clone());
+    let other_asset_before = owner.get_asset_balance(&other_asset).await.unwrap();
+
+    CallHandler::new_multi_call(owner.clone())
+        .add_call(owner_instance.methods().disprove(claim_ids[0]))
+        .add_call(owner_instance.methods().disprove(claim_ids[1]))
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call::<((), ())>()
+        .await
+        .expect("should be able to disprove both claims");
+
+    let contract_balances = instance.get_balances().await.unwrap();
+    assert_eq!(
+        contract_balances
+            .get(&AssetId::zeroed())
+            .copied()
+            .unwrap_or(0),
+        0
+    );
+    assert_eq!(contract_balances.get(&other_asset).copied().unwrap_or(0), 0);
+
+    assert_eq!(
+        owner.get_asset_balance(&other_asset).await.unwrap(),
+        other_asset_before + 10_000
+    );
+}
+
+#[tokio::test]
+async fn can_fulfill_claims_of_several_assets_in_one_call() {
+    let other_asset = AssetId::new([1; 32]);
+    let (instance, _id, mut wallets) =
+        get_contract_instance_with_assets(&[AssetId::zeroed(), other_asset]).await;
+
+    let owner = wallets.pop().unwrap();
+    let recipient = wallets.pop().unwrap();
+
+    let claim_ids = initiate_claims(
+        &instance,
+        &owner,
+        &recipient,
+        &[AssetId::zeroed(), other_asset],
+    )
+    .await;
+
+    test_support::produce_blocks(owner.provider().unwrap(), 120).await;
+
+    let recipient_instance = instance.clone().with_account(recipient.clone());
+    let other_asset_before = recipient.get_asset_balance(&other_asset).await.unwrap();
+
+    CallHandler::new_multi_call(recipient.clone())
+        .add_call(recipient_instance.methods().fulfill(claim_ids[0]))
+        .add_call(recipient_instance.methods().fulfill(claim_ids[1]))
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call::<((), ())>()
+        .await
+        .expect("should be able to fulfill both claims");
+
+    let contract_balances = instance.get_balances().await.unwrap();
+    assert_eq!(
+        contract_balances
+            .get(&AssetId::zeroed())
+            .copied()
+            .unwrap_or(0),
+        0
+    );
+    assert_eq!(contract_balances.get(&other_asset).copied().unwrap_or(0), 0);
+
+    assert_eq!(
+        recipient.get_asset_balance(&other_asset).await.unwrap(),
+        other_asset_before + 10_000
+    );
+}
@@ -0,0 +1,312 @@
+//! Random sequences of claims, disproves, fulfills and block production, checked
+//! against a model of what the contract should hold after each step.
+
+use std::collections::{BTreeMap, HashMap};
+
+use fuels::prelude::*;
+use proptest::{prelude::*, sample::Index};
+use test_support::{
+    claims_contract::{Claim, ClaimsContract},
+    Harness, CHALLENGE_PERIOD,
+};
+
+const WALLETS: usize = 3;
+
+fn assets() -> [AssetId; 2] {
+    [AssetId::zeroed(), AssetId::new([1; 32])]
+}
+
+/// Disproves and fulfills pick a claim by index into every ID handed out so far,
+/// plus one that doesn't exist yet.
+#[derive(Debug, Clone)]
+enum Op {
+    Initiate {
+        owner: usize,
+        recipient: usize,
+        asset: usize,
+        amount: u64,
+    },
+    Disprove {
+        by: usize,
+        claim: Index,
+    },
+    Fulfill {
+        by: usize,
+        claim: Index,
+    },
+    /// Disprove or fulfill an already resolved claim as the party that was
+    /// allowed to, which has to fail.
+    ResolveAgain {
+        fulfill: bool,
+        claim: Index,
+    },
+    ProduceBlocks(u32),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        3 => (0..WALLETS, 0..WALLETS, 0..assets().len(), 1..10_000u64).prop_map(
+            |(owner, recipient, asset, amount)| Op::Initiate {
+                owner,
+                recipient,
+                asset,
+                amount,
+            }
+        ),
+        2 => (0..WALLETS, any::<Index>()).prop_map(|(by, claim)| Op::Disprove { by, claim }),
+        2 => (0..WALLETS, any::<Index>()).prop_map(|(by, claim)| Op::Fulfill { by, claim }),
+        1 => (any::<bool>(), any::<Index>())
+            .prop_map(|(fulfill, claim)| Op::ResolveAgain { fulfill, claim }),
+        1 => (1..=CHALLENGE_PERIOD + 10).prop_map(Op::ProduceBlocks),
+    ]
+}
+
+/// What the contract should hold.
+#[derive(Default)]
+struct Model {
+    next_id: u64,
+    pending: BTreeMap<u64, Claim>,
+    resolved: BTreeMap<u64, Claim>,
+}
+
+impl Model {
+    fn pick(&self, claim: &Index) -> u64 {
+        claim.index(self.next_id as usize + 1) as u64
+    }
+
+    fn pick_resolved(&self, claim: &Index) -> Option<Claim> {
+        if self.resolved.is_empty() {
+            return None;
+        }
+
+        self.resolved
+            .values()
+            .nth(claim.index(self.resolved.len()))
+            .cloned()
+    }
+
+    fn resolve(&mut self, claim_id: u64) {
+        if let Some(claim) = self.pending.remove(&claim_id) {
+            self.resolved.insert(claim_id, claim);
+        }
+    }
+
+    fn balances(&self) -> HashMap<AssetId, u64> {
+        let mut balances = HashMap::new();
+        for claim in self.pending.values() {
+            *balances.entry(claim.asset).or_default() += claim.amount;
+        }
+        balances
+    }
+}
+
+struct Chain {
+    harness: Harness,
+    addresses: Vec<Address>,
+}
+
+impl Chain {
+    fn instance(&self, wallet: usize) -> ClaimsContract<WalletUnlocked> {
+        self.harness
+            .contract
+            .clone()
+            .with_account(self.harness.wallets[wallet].clone())
+    }
+
+    fn instance_of(&self, address: Address) -> ClaimsContract<WalletUnlocked> {
+        let wallet = self
+            .addresses
+            .iter()
+            .position(|wallet| *wallet == address)
+            .expect("claims should be between harness wallets");
+        self.instance(wallet)
+    }
+
+    /// Height of the block the next transaction goes into.
+    async fn next_height(&self) -> u32 {
+        self.harness.provider.latest_block_height().await.unwrap() + 1
+    }
+}
+
+async fn apply(chain: &Chain, model: &mut Model, op: &Op) {
+    match op {
+        Op::Initiate {
+            owner,
+            recipient,
+            asset,
+            amount,
+        } => {
+            let asset = assets()[*asset];
+            let block_height = chain.next_height().await;
+            let call_params = CallParameters::default()
+                .with_amount(*amount)
+                .with_asset_id(asset);
+
+            let claim_id = chain
+                .instance(*recipient)
+                .methods()
+                .initiate_claim(chain.addresses[*owner], chain.addresses[*recipient])
+                .call_params(call_params)
+                .unwrap()
+                .call()
+                .await
+                .expect("should be able to initiate claim")
+                .value;
+
+            assert_eq!(claim_id, model.next_id, "claim IDs should be sequential");
+            model.next_id += 1;
+            model.pending.insert(
+                claim_id,
+                Claim {
+                    id: claim_id,
+                    owner: chain.addresses[*owner],
+                    recipient: chain.addresses[*recipient],
+                    asset,
+                    amount: *amount,
+                    block_height,
+                },
+            );
+        }
+        Op::Disprove { by, claim } => {
+            let claim_id = model.pick(claim);
+            let allowed = model
+                .pending
+                .get(&claim_id)
+                .is_some_and(|claim| claim.owner == chain.addresses[*by]);
+
+            let res = chain
+                .instance(*by)
+                .methods()
+                .disprove(claim_id)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await;
+
+            assert_eq!(res.is_ok(), allowed, "disprove of {claim_id}: {res:?}");
+            if allowed {
+                model.resolve(claim_id);
+            }
+        }
+        Op::Fulfill { by, claim } => {
+            let claim_id = model.pick(claim);
+            let height = chain.next_height().await;
+            let allowed = model.pending.get(&claim_id).is_some_and(|claim| {
+                claim.recipient == chain.addresses[*by]
+                    && claim.block_height + CHALLENGE_PERIOD <= height
+            });
+
+            let res = chain
+                .instance(*by)
+                .methods()
+                .fulfill(claim_id)
+                .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                .call()
+                .await;
+
+            assert_eq!(res.is_ok(), allowed, "fulfill of {claim_id}: {res:?}");
+            if allowed {
+                model.resolve(claim_id);
+            }
+        }
+        Op::ResolveAgain { fulfill, claim } => {
+            let Some(claim) = model.pick_resolved(claim) else {
+                return;
+            };
+
+            let res = if *fulfill {
+                chain
+                    .instance_of(claim.recipient)
+                    .methods()
+                    .fulfill(claim.id)
+                    .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                    .call()
+                    .await
+            } else {
+                chain
+                    .instance_of(claim.owner)
+                    .methods()
+                    .disprove(claim.id)
+                    .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+                    .call()
+                    .await
+            };
+
+            assert!(
+                res.is_err(),
+                "resolved claim {} was resolved again: {res:?}",
+                claim.id
+            );
+        }
+        Op::ProduceBlocks(blocks) => {
+            test_support::produce_blocks(&chain.harness.provider, *blocks).await;
+        }
+    }
+}
+
+async fn check(chain: &Chain, model: &Model) {
+    let balances = chain.harness.contract.get_balances().await.unwrap();
+    let expected = model.balances();
+    for asset in assets() {
+        assert_eq!(
+            balances.get(&asset).copied().unwrap_or(0),
+            expected.get(&asset).copied().unwrap_or(0),
+            "contract balance of {asset} should equal its pending claims"
+        );
+    }
+
+    for owner in &chain.addresses {
+        let mut claims = chain
+            .harness
+            .contract
+            .methods()
+            .get_claims(*owner)
+            .simulate(Execution::Realistic)
+            .await
+            .unwrap()
+            .value;
+        claims.sort_by_key(|claim| claim.id);
+
+        let expected = model
+            .pending
+            .values()
+            .filter(|claim| claim.owner == *owner)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(claims, expected, "claims of {owner}");
+    }
+}
+
+async fn run(ops: Vec<Op>) {
+    let harness = Harness::builder()
+        .wallets(WALLETS)
+        .asset(assets()[1])
+        .coins(10, 1_000_000_000)
+        .build()
+        .await;
+    let addresses = harness
+        .wallets
+        .iter()
+        .map(|wallet| wallet.address().into())
+        .collect();
+    let chain = Chain { harness, addresses };
+    let mut model = Model::default();
+
+    for op in &ops {
+        apply(&chain, &mut model, op).await;
+        check(&chain, &model).await;
+    }
+}
+
+proptest! {
+    // Every case starts a node, so keep the number of cases modest.
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn contract_matches_model(ops in prop::collection::vec(op(), 1..24)) {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(run(ops));
+    }
+}
@@ -0,0 +1,86 @@
+//! Predicate address derivation.
+//!
+//! The make-claim script is configured with the claims contract and the owner,
+//! and the claimable predicate with the owner and the hash of that script. The
+//! owner's address is the root of the configured predicate bytecode.
+
+use fuels::{
+    tx::Input,
+    types::{Address, Bytes32, ContractId},
+};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    configurables::{offset, set_b256, Program},
+    CoreError,
+};
+
+/// Offsets and values of the make-claim script's configurables.
+pub fn script_configurables(
+    script_abi: &str,
+    contract_id: ContractId,
+    owner: Address,
+) -> Result<Vec<(usize, [u8; 32])>, CoreError> {
+    Ok(vec![
+        (offset(script_abi, "CLAIMS_CONTRACT_ADDRESS")?, *contract_id),
+        (offset(script_abi, "OWNER")?, *owner),
+    ])
+}
+
+pub fn script_bytecode(
+    script: Program,
+    contract_id: ContractId,
+    owner: Address,
+) -> Result<Vec<u8>, CoreError> {
+    let mut code = script.code.to_vec();
+    set_b256(
+        &mut code,
+        script.abi,
+        "CLAIMS_CONTRACT_ADDRESS",
+        *contract_id,
+    )?;
+    set_b256(&mut code, script.abi, "OWNER", *owner)?;
+
+    Ok(code)
+}
+
+/// Hash checked by the predicate's claim spend path, i.e. of the configured script.
+pub fn script_hash(
+    script: Program,
+    contract_id: ContractId,
+    owner: Address,
+) -> Result<Bytes32, CoreError> {
+    let code = script_bytecode(script, contract_id, owner)?;
+    let hash: [u8; 32] = Sha256::digest(code).into();
+
+    Ok(hash.into())
+}
+
+pub fn predicate_bytecode(
+    predicate: Program,
+    script_hash: Bytes32,
+    owner: Address,
+) -> Result<Vec<u8>, CoreError> {
+    let mut code = predicate.code.to_vec();
+    set_b256(
+        &mut code,
+        predicate.abi,
+        "MAKE_CLAIM_SCRIPT_HASH",
+        *script_hash,
+    )?;
+    set_b256(&mut code, predicate.abi, "OWNER", *owner)?;
+
+    Ok(code)
+}
+
+pub fn predicate_address(
+    predicate: Program,
+    script: Program,
+    contract_id: ContractId,
+    owner: Address,
+) -> Result<Address, CoreError> {
+    let script_hash = script_hash(script, contract_id, owner)?;
+    let code = predicate_bytecode(predicate, script_hash, owner)?;
+
+    Ok(Input::predicate_owner(code))
+}
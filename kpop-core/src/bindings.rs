@@ -0,0 +1,120 @@
+//! wasm-bindgen exports. Ids and keys are hex strings, coins and transactions JSON
+//! in the serde format of the Rust types.
+
+use std::str::FromStr;
+
+use fuels::{
+    crypto::SecretKey,
+    tx::Script,
+    types::{Address, AssetId, ChainId, ContractId},
+};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    address, claim,
+    configurables::Program,
+    tx::{self, Coin, Limits},
+};
+
+fn parse<T: FromStr>(what: &str, value: &str) -> Result<T, JsError> {
+    T::from_str(value).map_err(|_| JsError::new(&format!("invalid {what}: {value}")))
+}
+
+/// Hex address of `owner`'s claimable predicate. The ABIs have to come from the
+/// same build as the bytecode.
+#[wasm_bindgen(js_name = predicateAddress)]
+pub fn predicate_address(
+    predicate_code: &[u8],
+    predicate_abi: &str,
+    script_code: &[u8],
+    script_abi: &str,
+    contract_id: &str,
+    owner: &str,
+) -> Result<String, JsError> {
+    let address = address::predicate_address(
+        Program {
+            code: predicate_code,
+            abi: predicate_abi,
+        },
+        Program {
+            code: script_code,
+            abi: script_abi,
+        },
+        parse::<ContractId>("contract id", contract_id)?,
+        parse::<Address>("address", owner)?,
+    )?;
+
+    Ok(format!("{address:#x}"))
+}
+
+/// Configured predicate bytecode, needed as input data when spending.
+#[wasm_bindgen(js_name = predicateBytecode)]
+pub fn predicate_bytecode(
+    predicate_code: &[u8],
+    predicate_abi: &str,
+    script_code: &[u8],
+    script_abi: &str,
+    contract_id: &str,
+    owner: &str,
+) -> Result<Vec<u8>, JsError> {
+    let contract_id = parse::<ContractId>("contract id", contract_id)?;
+    let owner = parse::<Address>("address", owner)?;
+    let script = Program {
+        code: script_code,
+        abi: script_abi,
+    };
+    let script_hash = address::script_hash(script, contract_id, owner)?;
+
+    Ok(address::predicate_bytecode(
+        Program {
+            code: predicate_code,
+            abi: predicate_abi,
+        },
+        script_hash,
+        owner,
+    )?)
+}
+
+/// JSON transaction spending `amount` from the predicate to `recipient`.
+#[wasm_bindgen(js_name = buildPredicateSpend)]
+pub fn build_predicate_spend(
+    predicate_code: &[u8],
+    coins_json: &str,
+    recipient: &str,
+    asset_id: &str,
+    amount: u64,
+    script_gas_limit: u64,
+    max_fee: u64,
+) -> Result<String, JsError> {
+    let coins: Vec<Coin> = serde_json::from_str(coins_json)?;
+    let tx = tx::build_predicate_spend(
+        predicate_code,
+        &coins,
+        parse("address", recipient)?,
+        parse::<AssetId>("asset id", asset_id)?,
+        amount,
+        Limits {
+            script_gas_limit,
+            max_fee,
+        },
+    )?;
+
+    Ok(serde_json::to_string(&tx)?)
+}
+
+/// Sign witness 0 of a JSON transaction, returning the signed JSON.
+#[wasm_bindgen(js_name = signTransaction)]
+pub fn sign_transaction(tx_json: &str, secret_key: &str, chain_id: u64) -> Result<String, JsError> {
+    let mut tx: Script = serde_json::from_str(tx_json)?;
+    let secret_key = parse::<SecretKey>("secret key", secret_key)?;
+
+    tx::sign(&mut tx, &secret_key, ChainId::new(chain_id));
+
+    Ok(serde_json::to_string(&tx)?)
+}
+
+/// JSON claims decoded from `get_claims` return data.
+#[wasm_bindgen(js_name = decodeClaims)]
+pub fn decode_claims(data: &[u8]) -> Result<String, JsError> {
+    Ok(serde_json::to_string(&claim::decode_claims(data)?)?)
+}
@@ -0,0 +1,129 @@
+//! Decoding `get_claims` return data without the abigen bindings.
+
+use fuels::types::{Address, AssetId};
+use serde::{Deserialize, Serialize};
+
+use crate::CoreError;
+
+/// Mirror of the contract's `Claim`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub id: u64,
+    pub owner: Address,
+    pub recipient: Address,
+    pub asset: AssetId,
+    pub amount: u64,
+    pub block_height: u32,
+}
+
+/// id, owner, recipient, asset, amount, block_height
+const CLAIM_SIZE: usize = 8 + 32 + 32 + 32 + 8 + 4;
+
+/// Decode a `Vec<Claim>` as returned by `get_claims`: a big endian `u64` length
+/// followed by the claims, each field in declaration order.
+pub fn decode_claims(data: &[u8]) -> Result<Vec<Claim>, CoreError> {
+    let invalid = || CoreError::InvalidClaimData(data.len());
+
+    let (len, mut rest) = data.split_first_chunk::<8>().ok_or_else(invalid)?;
+    let len = usize::try_from(u64::from_be_bytes(*len)).map_err(|_| invalid())?;
+    if rest.len() != len.checked_mul(CLAIM_SIZE).ok_or_else(invalid)? {
+        return Err(invalid());
+    }
+
+    let mut claims = Vec::with_capacity(len);
+    while let Some((claim, tail)) = rest.split_first_chunk::<CLAIM_SIZE>() {
+        claims.push(decode_claim(claim));
+        rest = tail;
+    }
+
+    Ok(claims)
+}
+
+fn decode_claim(bytes: &[u8; CLAIM_SIZE]) -> Claim {
+    let mut reader = Reader(bytes);
+
+    Claim {
+        id: u64::from_be_bytes(reader.take()),
+        owner: Address::new(reader.take()),
+        recipient: Address::new(reader.take()),
+        asset: AssetId::new(reader.take()),
+        amount: u64::from_be_bytes(reader.take()),
+        block_height: u32::from_be_bytes(reader.take()),
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, tail) = self
+            .0
+            .split_first_chunk::<N>()
+            .expect("claim should have been length checked");
+        self.0 = tail;
+
+        *head
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(claims: &[Claim]) -> Vec<u8> {
+        let mut data = (claims.len() as u64).to_be_bytes().to_vec();
+        for claim in claims {
+            data.extend(claim.id.to_be_bytes());
+            data.extend(*claim.owner);
+            data.extend(*claim.recipient);
+            data.extend(*claim.asset);
+            data.extend(claim.amount.to_be_bytes());
+            data.extend(claim.block_height.to_be_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn decodes_claims() {
+        let claims = vec![
+            Claim {
+                id: 1,
+                owner: Address::new([1; 32]),
+                recipient: Address::new([2; 32]),
+                asset: AssetId::new([3; 32]),
+                amount: 10_000,
+                block_height: 7,
+            },
+            Claim {
+                id: 2,
+                owner: Address::new([1; 32]),
+                recipient: Address::new([4; 32]),
+                asset: AssetId::BASE,
+                amount: 5,
+                block_height: 130,
+            },
+        ];
+
+        assert_eq!(decode_claims(&encode(&claims)).unwrap(), claims);
+        assert_eq!(decode_claims(&encode(&[])).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let claim = Claim {
+            id: 1,
+            owner: Address::zeroed(),
+            recipient: Address::zeroed(),
+            asset: AssetId::BASE,
+            amount: 1,
+            block_height: 1,
+        };
+        let data = encode(&[claim]);
+
+        assert_eq!(
+            decode_claims(&data[..data.len() - 1]),
+            Err(CoreError::InvalidClaimData(data.len() - 1))
+        );
+        assert!(decode_claims(&[0; 4]).is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Writing configurable constants into bytecode.
+//!
+//! Configurables live at fixed offsets in the compiled binary, listed in the ABI.
+//! All of ours are `b256` or `Address`, which encode as their 32 raw bytes.
+//!
+//! Offsets move whenever a program is rebuilt, so bytecode always comes with the
+//! ABI it was compiled alongside.
+
+use serde::Deserialize;
+
+use crate::CoreError;
+
+/// Compiled bytecode and the JSON ABI produced by the same build.
+#[derive(Debug, Clone, Copy)]
+pub struct Program<'a> {
+    pub code: &'a [u8],
+    pub abi: &'a str,
+}
+
+#[derive(Deserialize)]
+struct Abi {
+    #[serde(default)]
+    configurables: Vec<Configurable>,
+}
+
+#[derive(Deserialize)]
+struct Configurable {
+    name: String,
+    offset: usize,
+}
+
+/// Offset of the configurable `name` in the bytecode `abi` describes.
+pub fn offset(abi: &str, name: &'static str) -> Result<usize, CoreError> {
+    let abi: Abi =
+        serde_json::from_str(abi).map_err(|err| CoreError::InvalidAbi(err.to_string()))?;
+
+    abi.configurables
+        .iter()
+        .find(|configurable| configurable.name == name)
+        .map(|configurable| configurable.offset)
+        .ok_or(CoreError::MissingConfigurable(name))
+}
+
+/// Overwrite the 32 byte configurable `name` in `code` with `value`.
+pub fn set_b256(
+    code: &mut [u8],
+    abi: &str,
+    name: &'static str,
+    value: [u8; 32],
+) -> Result<(), CoreError> {
+    let offset = offset(abi, name)?;
+
+    let slot = code
+        .get_mut(offset..offset + value.len())
+        .ok_or_else(|| CoreError::InvalidAbi(format!("{name} is outside the bytecode")))?;
+    slot.copy_from_slice(&value);
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Provider-agnostic parts of kpop.
+//!
+//! Everything here works on bytecode, coins and receipts handed in by the caller,
+//! so it compiles to `wasm32-unknown-unknown` and can run in a browser wallet. The
+//! `kpop` crate wraps it with a provider, tokio and the CLI.
+
+pub mod address;
+pub mod claim;
+pub mod configurables;
+pub mod tx;
+
+#[cfg(feature = "wasm")]
+mod bindings;
+
+use std::fmt;
+
+/// Blocks a claim has to wait before it can be fulfilled, see `fulfill` in the
+/// claims contract.
+pub const CHALLENGE_PERIOD: u32 = 120;
+
+/// Keys of the wallets funded on local nodes, the devnet and the test harness,
+/// fixed so addresses are recognisable across runs. Never use them elsewhere.
+pub const PREFUNDED_KEYS: [&str; 3] = [
+    "0xc2620849458064e8f1eb2bc4c459f473695b443ac3134c82ddd4fd992bd138fd",
+    "0x37fa81c84ccd547c30c176b118d5cb892bdb113e8e80141f266519422ef9eefd",
+    "0x976e5c3fa620092c718d852ca703b6da9e3075b9f2ecb8ed42d9f746bf26aafb",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreError {
+    MissingConfigurable(&'static str),
+    InvalidAbi(String),
+    InvalidClaimData(usize),
+    InsufficientCoins { asset_id: String, needed: u64 },
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CoreError::MissingConfigurable(name) => write!(f, "ABI has no configurable {name}"),
+            CoreError::InvalidAbi(err) => write!(f, "invalid ABI: {err}"),
+            CoreError::InvalidClaimData(len) => {
+                write!(f, "{len} bytes is not a valid encoding of a list of claims")
+            }
+            CoreError::InsufficientCoins { asset_id, needed } => {
+                write!(f, "coins don't cover {needed} of {asset_id}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
@@ -0,0 +1,224 @@
+//! Building and signing predicate transactions from coins the caller looked up.
+//!
+//! Predicate gas is left at zero, the node or a dry run has to estimate it before
+//! submitting. Witness 0 is a zeroed placeholder until [`sign`] fills it in.
+
+use std::collections::BTreeSet;
+
+use fuels::{
+    crypto::{Message, SecretKey, Signature},
+    tx::{
+        field::Witnesses, policies::Policies, Input, Output, Script, Transaction, TxPointer,
+        UniqueIdentifier, UtxoId, Witness,
+    },
+    types::{Address, AssetId, ChainId},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::CoreError;
+
+pub const SIGNER_WITNESS_INDEX: u16 = 0;
+
+const SIGNATURE_SIZE: usize = 64;
+
+/// A spendable coin, as returned by the node's `coinsToSpend` query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coin {
+    pub utxo_id: UtxoId,
+    pub amount: u64,
+    pub asset_id: AssetId,
+}
+
+/// Gas limit and fee cap, normally taken from an estimate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Limits {
+    pub script_gas_limit: u64,
+    pub max_fee: u64,
+}
+
+/// Owner spend of `amount` of `asset_id` from the predicate to `recipient`.
+///
+/// Change of every asset in `coins` goes back to the predicate, so `coins` has to
+/// include base asset coins for the fee.
+pub fn build_predicate_spend(
+    predicate_code: &[u8],
+    coins: &[Coin],
+    recipient: Address,
+    asset_id: AssetId,
+    amount: u64,
+    limits: Limits,
+) -> Result<Script, CoreError> {
+    check_covers(coins, asset_id, amount)?;
+
+    let predicate = Input::predicate_owner(predicate_code);
+    let inputs = predicate_inputs(predicate_code, coins);
+
+    let mut outputs = vec![Output::coin(recipient, amount, asset_id)];
+    outputs.extend(change_outputs(predicate, coins));
+
+    Ok(script(vec![], vec![], inputs, outputs, limits))
+}
+
+/// Fill witness 0 with `secret_key`'s signature of the transaction ID.
+pub fn sign(tx: &mut Script, secret_key: &SecretKey, chain_id: ChainId) {
+    let signature = Signature::sign(secret_key, &Message::from_bytes(*tx.id(&chain_id)));
+    tx.witnesses_mut()[SIGNER_WITNESS_INDEX as usize] = Witness::from(signature.as_ref().to_vec());
+}
+
+pub fn placeholder_witness() -> Witness {
+    Witness::from(vec![0; SIGNATURE_SIZE])
+}
+
+fn check_covers(coins: &[Coin], asset_id: AssetId, amount: u64) -> Result<(), CoreError> {
+    let available: u64 = coins
+        .iter()
+        .filter(|coin| coin.asset_id == asset_id)
+        .map(|coin| coin.amount)
+        .sum();
+
+    if available < amount {
+        return Err(CoreError::InsufficientCoins {
+            asset_id: asset_id.to_string(),
+            needed: amount,
+        });
+    }
+
+    Ok(())
+}
+
+fn predicate_inputs(predicate_code: &[u8], coins: &[Coin]) -> Vec<Input> {
+    let predicate = Input::predicate_owner(predicate_code);
+
+    coins
+        .iter()
+        .map(|coin| {
+            Input::coin_predicate(
+                coin.utxo_id,
+                predicate,
+                coin.amount,
+                coin.asset_id,
+                TxPointer::default(),
+                0,
+                predicate_code.to_vec(),
+                vec![],
+            )
+        })
+        .collect()
+}
+
+fn change_outputs(to: Address, coins: &[Coin]) -> Vec<Output> {
+    coins
+        .iter()
+        .map(|coin| coin.asset_id)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|asset_id| Output::change(to, 0, asset_id))
+        .collect()
+}
+
+fn script(
+    script: Vec<u8>,
+    script_data: Vec<u8>,
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    limits: Limits,
+) -> Script {
+    Transaction::script(
+        limits.script_gas_limit,
+        script,
+        script_data,
+        Policies::new().with_max_fee(limits.max_fee),
+        inputs,
+        outputs,
+        vec![placeholder_witness()],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::tx::field::{Inputs, Outputs};
+
+    use super::*;
+
+    fn coin(asset_id: AssetId, amount: u64) -> Coin {
+        Coin {
+            utxo_id: UtxoId::new([amount as u8; 32].into(), 0),
+            amount,
+            asset_id,
+        }
+    }
+
+    #[test]
+    fn predicate_spend_returns_change_of_every_asset() {
+        let asset = AssetId::new([7; 32]);
+        let coins = [coin(asset, 10), coin(AssetId::BASE, 5)];
+        let predicate = Input::predicate_owner([1, 2, 3]);
+
+        let tx = build_predicate_spend(
+            &[1, 2, 3],
+            &coins,
+            Address::new([9; 32]),
+            asset,
+            8,
+            Limits {
+                script_gas_limit: 0,
+                max_fee: 1,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(tx.inputs().len(), 2);
+        assert!(tx
+            .outputs()
+            .contains(&Output::coin(Address::new([9; 32]), 8, asset)));
+        assert!(tx.outputs().contains(&Output::change(predicate, 0, asset)));
+        assert!(tx
+            .outputs()
+            .contains(&Output::change(predicate, 0, AssetId::BASE)));
+    }
+
+    #[test]
+    fn predicate_spend_checks_coins_cover_amount() {
+        let err = build_predicate_spend(
+            &[1, 2, 3],
+            &[coin(AssetId::BASE, 5)],
+            Address::zeroed(),
+            AssetId::BASE,
+            6,
+            Limits {
+                script_gas_limit: 0,
+                max_fee: 1,
+            },
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            CoreError::InsufficientCoins { needed: 6, .. }
+        ));
+    }
+
+    #[test]
+    fn signing_keeps_tx_id() {
+        let secret_key = SecretKey::try_from([1; 32].as_slice()).unwrap();
+        let chain_id = ChainId::default();
+        let mut tx = build_predicate_spend(
+            &[1, 2, 3],
+            &[coin(AssetId::BASE, 5)],
+            Address::zeroed(),
+            AssetId::BASE,
+            1,
+            Limits {
+                script_gas_limit: 0,
+                max_fee: 1,
+            },
+        )
+        .unwrap();
+        let id = tx.id(&chain_id);
+
+        sign(&mut tx, &secret_key, chain_id);
+
+        assert_eq!(tx.id(&chain_id), id);
+        assert_ne!(tx.witnesses()[0], placeholder_witness());
+    }
+}
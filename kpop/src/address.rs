@@ -0,0 +1,182 @@
+//! Parsing and rendering of addresses and contract identities.
+//!
+//! Everywhere an address is expected kpop accepts bech32 (`fuel1...`), hex with
+//! or without `0x`, or the name of an address book entry. Contracts are written
+//! as `contract:<hex>`, and can only be given where a contract makes sense, e.g.
+//! as the recipient of `send-to`.
+//!
+//! Addresses are always shown as bech32 and contracts as `contract:<hex>`, which
+//! parse back to the same identity. Where an address book is at hand, known
+//! identities are shown with their name as well.
+
+use std::{path::PathBuf, str::FromStr};
+
+use fuels::types::{bech32::Bech32Address, Address, ContractId, Identity};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ADDRESS_BOOK_PATH: &str = "./contacts.json";
+
+const CONTRACT_PREFIX: &str = "contract:";
+
+/// Parse a name from `book`, a bech32 or hex address, or `contract:<hex>`.
+pub fn parse_identity(s: &str, book: &AddressBook) -> Result<Identity, String> {
+    match book.get(s) {
+        Some(identity) => Ok(identity),
+        None => parse_literal(s),
+    }
+}
+
+fn parse_literal(s: &str) -> Result<Identity, String> {
+    if let Some(contract_id) = s.strip_prefix(CONTRACT_PREFIX) {
+        return ContractId::from_str(contract_id)
+            .map(Identity::ContractId)
+            .map_err(|_| format!("invalid contract ID {contract_id}"));
+    }
+
+    Bech32Address::from_str(s)
+        .map(Address::from)
+        .or_else(|_| Address::from_str(s))
+        .map(Identity::Address)
+        .map_err(|_| format!("{s} is not an address, contract or known name"))
+}
+
+/// Like [`parse_identity`], but contracts are rejected.
+pub fn parse_address(s: &str, book: &AddressBook) -> Result<Address, String> {
+    match parse_identity(s, book)? {
+        Identity::Address(address) => Ok(address),
+        Identity::ContractId(_) => Err(format!("{s} is a contract, expected an address")),
+    }
+}
+
+pub fn display_address(address: &Address) -> String {
+    Bech32Address::from(*address).to_string()
+}
+
+pub fn display_identity(identity: &Identity) -> String {
+    match identity {
+        Identity::Address(address) => display_address(address),
+        Identity::ContractId(contract_id) => format!("{CONTRACT_PREFIX}{contract_id}"),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AddressBookFile {
+    contacts: Vec<ContactEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContactEntry {
+    name: String,
+    address: String,
+}
+
+/// Names for addresses and contracts.
+#[derive(Debug, Clone)]
+pub struct AddressBook {
+    path: PathBuf,
+    contacts: Vec<(String, Identity)>,
+}
+
+impl AddressBook {
+    /// Load the address book at `path`. A missing file is an empty address book.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        let file: AddressBookFile = if path.exists() {
+            let contents =
+                std::fs::read_to_string(&path).expect("should be able to read address book");
+            serde_json::from_str(&contents).expect("address book should be valid json")
+        } else {
+            AddressBookFile::default()
+        };
+
+        let contacts = file
+            .contacts
+            .into_iter()
+            .map(|entry| {
+                let identity = parse_literal(&entry.address)
+                    .expect("address book should contain valid addresses");
+                (entry.name, identity)
+            })
+            .collect();
+
+        Self { path, contacts }
+    }
+
+    pub fn contacts(&self) -> &[(String, Identity)] {
+        &self.contacts
+    }
+
+    /// The identity with this name, ignoring case.
+    pub fn get(&self, name: &str) -> Option<Identity> {
+        self.contacts
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            .map(|(_, identity)| *identity)
+    }
+
+    /// The name of `identity`, if it has one.
+    pub fn name(&self, identity: &Identity) -> Option<&str> {
+        self.contacts
+            .iter()
+            .find(|(_, known)| known == identity)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Add or replace the entry with the same name.
+    ///
+    /// Names that would parse as an address or contract are rejected, they could
+    /// never be looked up.
+    pub fn add(&mut self, name: &str, identity: Identity) -> Result<(), String> {
+        if name.is_empty() || parse_literal(name).is_ok() {
+            return Err(format!("{name} can't be used as a name"));
+        }
+
+        self.remove(name);
+        self.contacts.push((name.to_string(), identity));
+        Ok(())
+    }
+
+    /// Returns false if the name wasn't known.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.contacts.len();
+        self.contacts
+            .retain(|(known, _)| !known.eq_ignore_ascii_case(name));
+        self.contacts.len() != len
+    }
+
+    /// e.g. `alice (fuel1...)`, or just the address if it has no name.
+    pub fn display_identity(&self, identity: &Identity) -> String {
+        match self.name(identity) {
+            Some(name) => format!("{name} ({})", display_identity(identity)),
+            None => display_identity(identity),
+        }
+    }
+
+    pub fn display_address(&self, address: &Address) -> String {
+        self.display_identity(&Identity::Address(*address))
+    }
+
+    pub fn save(&self) {
+        let file = AddressBookFile {
+            contacts: self
+                .contacts
+                .iter()
+                .map(|(name, identity)| ContactEntry {
+                    name: name.clone(),
+                    address: display_identity(identity),
+                })
+                .collect(),
+        };
+
+        let contents =
+            serde_json::to_string_pretty(&file).expect("should be able to encode address book");
+        std::fs::write(&self.path, contents).expect("should be able to write address book");
+    }
+}
+
+impl Default for AddressBook {
+    fn default() -> Self {
+        Self::load(DEFAULT_ADDRESS_BOOK_PATH)
+    }
+}
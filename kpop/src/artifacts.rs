@@ -0,0 +1,302 @@
+//! Named versions of the claimable predicate and the make-claim script.
+//!
+//! An owner's predicate address is derived from the predicate bytecode, which is
+//! configured with the hash of the make-claim script. Rebuilding either binary
+//! therefore moves every owner to a new address. Released binaries are snapshotted
+//! into an artifacts directory together with their ABIs and sha256 hashes, so kpop
+//! can keep deriving addresses for old versions and move funds away from them.
+//!
+//! Configurable offsets differ between builds, so each version is configured with
+//! the offsets from its own ABI rather than those of the current build.
+
+use std::path::{Path, PathBuf};
+
+use kpop_core::configurables::Program;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::ArtifactError;
+
+pub const DEFAULT_ARTIFACTS_DIR: &str = "./artifacts";
+
+const DEV_PREDICATE_PATH: &str = "../claimable/out/debug/claimable.bin";
+const DEV_PREDICATE_ABI_PATH: &str = "../claimable/out/debug/claimable-abi.json";
+const DEV_SCRIPT_PATH: &str = "../make-claim/out/debug/make-claim.bin";
+const DEV_SCRIPT_ABI_PATH: &str = "../make-claim/out/debug/make-claim-abi.json";
+const DEV_VERSION_NAME: &str = "dev";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const PREDICATE_FILE: &str = "claimable.bin";
+const PREDICATE_ABI_FILE: &str = "claimable-abi.json";
+const SCRIPT_FILE: &str = "make-claim.bin";
+const SCRIPT_ABI_FILE: &str = "make-claim-abi.json";
+
+/// On-disk description of all pinned versions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub current: Option<String>,
+    pub versions: Vec<PinnedVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinnedVersion {
+    pub name: String,
+    pub predicate_sha256: String,
+    pub predicate_abi_sha256: String,
+    pub script_sha256: String,
+    pub script_abi_sha256: String,
+}
+
+/// A predicate and script pair that can be loaded, each with its ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactVersion {
+    pub name: String,
+    pub predicate_path: PathBuf,
+    pub predicate_abi_path: PathBuf,
+    pub script_path: PathBuf,
+    pub script_abi_path: PathBuf,
+}
+
+impl ArtifactVersion {
+    pub fn predicate(&self) -> Result<ProgramFiles, ArtifactError> {
+        ProgramFiles::read(&self.predicate_path, &self.predicate_abi_path)
+    }
+
+    pub fn script(&self) -> Result<ProgramFiles, ArtifactError> {
+        ProgramFiles::read(&self.script_path, &self.script_abi_path)
+    }
+}
+
+/// Bytecode and ABI of one program of a version, read from disk.
+#[derive(Debug, Clone)]
+pub struct ProgramFiles {
+    pub code: Vec<u8>,
+    pub abi: String,
+}
+
+impl ProgramFiles {
+    fn read(code_path: &Path, abi_path: &Path) -> Result<Self, ArtifactError> {
+        Ok(Self {
+            code: std::fs::read(code_path)
+                .map_err(|err| ArtifactError::unreadable(code_path, err))?,
+            abi: std::fs::read_to_string(abi_path)
+                .map_err(|err| ArtifactError::unreadable(abi_path, err))?,
+        })
+    }
+
+    pub fn program(&self) -> Program<'_> {
+        Program {
+            code: &self.code,
+            abi: &self.abi,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Artifacts {
+    /// `None` when only the local debug builds are known.
+    dir: Option<PathBuf>,
+    manifest: Manifest,
+}
+
+impl Artifacts {
+    /// Only the local debug builds, nothing pinned.
+    pub fn dev() -> Self {
+        Self {
+            dir: None,
+            manifest: Manifest::default(),
+        }
+    }
+
+    /// Load the manifest in `dir`, verifying the hashes of every pinned file.
+    ///
+    /// A missing manifest is treated as "nothing pinned yet", in which case the
+    /// local debug builds are used as the only known version and have to be
+    /// readable.
+    pub fn load(dir: impl Into<PathBuf>) -> Result<Self, ArtifactError> {
+        let dir = dir.into();
+        let manifest_path = dir.join(MANIFEST_FILE);
+
+        let manifest: Manifest = if manifest_path.exists() {
+            let contents = std::fs::read_to_string(&manifest_path)
+                .map_err(|err| ArtifactError::unreadable(&manifest_path, err))?;
+            serde_json::from_str(&contents)
+                .map_err(|err| ArtifactError::InvalidManifest(err.to_string()))?
+        } else {
+            Manifest::default()
+        };
+
+        if let Some(current) = &manifest.current {
+            if !manifest
+                .versions
+                .iter()
+                .any(|pinned| pinned.name == *current)
+            {
+                return Err(ArtifactError::UnknownCurrent(current.clone()));
+            }
+        }
+
+        let artifacts = Self {
+            dir: Some(dir),
+            manifest,
+        };
+
+        for pinned in &artifacts.manifest.versions {
+            let version = artifacts.pinned_version(pinned);
+            for (path, sha256) in [
+                (&version.predicate_path, &pinned.predicate_sha256),
+                (&version.predicate_abi_path, &pinned.predicate_abi_sha256),
+                (&version.script_path, &pinned.script_sha256),
+                (&version.script_abi_path, &pinned.script_abi_sha256),
+            ] {
+                if sha256_hex(path)? != *sha256 {
+                    return Err(ArtifactError::HashMismatch {
+                        version: pinned.name.clone(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+
+        if artifacts.manifest.versions.is_empty() {
+            let dev = dev_version();
+            dev.predicate()?;
+            dev.script()?;
+        }
+
+        Ok(artifacts)
+    }
+
+    /// The version new funds should be sent to.
+    pub fn current(&self) -> ArtifactVersion {
+        match &self.manifest.current {
+            Some(name) => self
+                .version(name)
+                .expect("current version is checked to be pinned on load"),
+            None => dev_version(),
+        }
+    }
+
+    /// Every known version, oldest first.
+    pub fn versions(&self) -> Vec<ArtifactVersion> {
+        if self.manifest.versions.is_empty() {
+            return vec![dev_version()];
+        }
+
+        self.manifest
+            .versions
+            .iter()
+            .map(|pinned| self.pinned_version(pinned))
+            .collect()
+    }
+
+    /// Every known version except the current one.
+    pub fn previous(&self) -> Vec<ArtifactVersion> {
+        let current = self.current();
+
+        self.versions()
+            .into_iter()
+            .filter(|version| *version != current)
+            .collect()
+    }
+
+    pub fn version(&self, name: &str) -> Option<ArtifactVersion> {
+        self.manifest
+            .versions
+            .iter()
+            .find(|pinned| pinned.name == name)
+            .map(|pinned| self.pinned_version(pinned))
+    }
+
+    /// Snapshot the local debug builds as version `name` and make it current.
+    pub fn pin(&mut self, name: &str) -> Result<ArtifactVersion, ArtifactError> {
+        self.pin_from(name, &dev_version())
+    }
+
+    /// Snapshot the files of `source` as version `name` and make it current.
+    ///
+    /// Nothing is pinned if any file can't be copied, though files copied before
+    /// that are left in the version's directory.
+    pub fn pin_from(
+        &mut self,
+        name: &str,
+        source: &ArtifactVersion,
+    ) -> Result<ArtifactVersion, ArtifactError> {
+        if self.version(name).is_some() {
+            return Err(ArtifactError::AlreadyPinned(name.to_string()));
+        }
+
+        let dir = self
+            .dir
+            .clone()
+            .expect("artifacts should be loaded from a directory to pin versions");
+        let version_dir = dir.join(name);
+        std::fs::create_dir_all(&version_dir)
+            .map_err(|err| ArtifactError::unwritable(&version_dir, err))?;
+
+        for (from, file) in [
+            (&source.predicate_path, PREDICATE_FILE),
+            (&source.predicate_abi_path, PREDICATE_ABI_FILE),
+            (&source.script_path, SCRIPT_FILE),
+            (&source.script_abi_path, SCRIPT_ABI_FILE),
+        ] {
+            let contents =
+                std::fs::read(from).map_err(|err| ArtifactError::unreadable(from, err))?;
+            let to = version_dir.join(file);
+            std::fs::write(&to, contents).map_err(|err| ArtifactError::unwritable(&to, err))?;
+        }
+
+        let hash = |file| sha256_hex(&version_dir.join(file));
+        let pinned = PinnedVersion {
+            name: name.to_string(),
+            predicate_sha256: hash(PREDICATE_FILE)?,
+            predicate_abi_sha256: hash(PREDICATE_ABI_FILE)?,
+            script_sha256: hash(SCRIPT_FILE)?,
+            script_abi_sha256: hash(SCRIPT_ABI_FILE)?,
+        };
+
+        let mut manifest = self.manifest.clone();
+        manifest.versions.push(pinned);
+        manifest.current = Some(name.to_string());
+
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let contents =
+            serde_json::to_string_pretty(&manifest).expect("should be able to encode manifest");
+        std::fs::write(&manifest_path, contents)
+            .map_err(|err| ArtifactError::unwritable(&manifest_path, err))?;
+
+        self.manifest = manifest;
+        Ok(self.current())
+    }
+
+    fn pinned_version(&self, pinned: &PinnedVersion) -> ArtifactVersion {
+        let version_dir = self
+            .dir
+            .as_ref()
+            .expect("pinned versions are only loaded from a directory")
+            .join(&pinned.name);
+
+        ArtifactVersion {
+            name: pinned.name.clone(),
+            predicate_path: version_dir.join(PREDICATE_FILE),
+            predicate_abi_path: version_dir.join(PREDICATE_ABI_FILE),
+            script_path: version_dir.join(SCRIPT_FILE),
+            script_abi_path: version_dir.join(SCRIPT_ABI_FILE),
+        }
+    }
+}
+
+pub fn dev_version() -> ArtifactVersion {
+    ArtifactVersion {
+        name: DEV_VERSION_NAME.to_string(),
+        predicate_path: DEV_PREDICATE_PATH.into(),
+        predicate_abi_path: DEV_PREDICATE_ABI_PATH.into(),
+        script_path: DEV_SCRIPT_PATH.into(),
+        script_abi_path: DEV_SCRIPT_ABI_PATH.into(),
+    }
+}
+
+fn sha256_hex(path: &Path) -> Result<String, ArtifactError> {
+    let bytes = std::fs::read(path).map_err(|err| ArtifactError::unreadable(path, err))?;
+    Ok(hex::encode(Sha256::digest(bytes)))
+}
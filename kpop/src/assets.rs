@@ -0,0 +1,192 @@
+//! Known assets, so amounts can be written and shown in whole units.
+//!
+//! On chain every amount is an integer number of base units. The registry maps a
+//! symbol to an asset ID and the number of decimals its base units have, e.g. 9
+//! for ETH, so `1.5 ETH` is 1500000000. Assets that aren't registered have no
+//! decimals and are shown by ID, which keeps raw amounts working unchanged.
+
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use fuels::types::AssetId;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_ASSETS_PATH: &str = "./assets.json";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetInfo {
+    pub symbol: String,
+    pub asset_id: AssetId,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AssetsFile {
+    assets: Vec<AssetEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AssetEntry {
+    symbol: String,
+    asset_id: String,
+    decimals: u8,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssetRegistry {
+    path: PathBuf,
+    assets: Vec<AssetInfo>,
+}
+
+impl AssetRegistry {
+    /// Load the registry at `path`. A missing file is an empty registry.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        let file: AssetsFile = if path.exists() {
+            let contents =
+                std::fs::read_to_string(&path).expect("should be able to read asset registry");
+            serde_json::from_str(&contents).expect("asset registry should be valid json")
+        } else {
+            AssetsFile::default()
+        };
+
+        let assets = file
+            .assets
+            .into_iter()
+            .map(|entry| AssetInfo {
+                asset_id: AssetId::from_str(&entry.asset_id)
+                    .expect("asset registry should contain valid ids"),
+                symbol: entry.symbol,
+                decimals: entry.decimals,
+            })
+            .collect();
+
+        Self { path, assets }
+    }
+
+    pub fn assets(&self) -> &[AssetInfo] {
+        &self.assets
+    }
+
+    /// Add or replace the asset with the same symbol.
+    pub fn add(&mut self, asset: AssetInfo) {
+        self.assets
+            .retain(|known| !known.symbol.eq_ignore_ascii_case(&asset.symbol));
+        self.assets.push(asset);
+    }
+
+    /// Returns false if the symbol wasn't known.
+    pub fn remove(&mut self, symbol: &str) -> bool {
+        let len = self.assets.len();
+        self.assets
+            .retain(|known| !known.symbol.eq_ignore_ascii_case(symbol));
+        self.assets.len() != len
+    }
+
+    /// Look up a symbol (case insensitive) or a hex asset ID.
+    pub fn resolve(&self, asset: &str) -> Option<AssetId> {
+        self.assets
+            .iter()
+            .find(|known| known.symbol.eq_ignore_ascii_case(asset))
+            .map(|known| known.asset_id)
+            .or_else(|| AssetId::from_str(asset).ok())
+    }
+
+    pub fn info(&self, asset_id: &AssetId) -> Option<&AssetInfo> {
+        self.assets.iter().find(|known| known.asset_id == *asset_id)
+    }
+
+    /// Decimals of `asset_id`, none if it isn't registered.
+    pub fn decimals(&self, asset_id: &AssetId) -> u8 {
+        self.info(asset_id).map(|info| info.decimals).unwrap_or(0)
+    }
+
+    pub fn parse_amount(&self, asset_id: &AssetId, amount: &str) -> Result<u64, String> {
+        parse_amount(amount, self.decimals(asset_id))
+    }
+
+    /// e.g. `1.5 ETH (1500000000)`, or `1500 of <asset id>` if not registered.
+    pub fn display(&self, asset_id: &AssetId, amount: u64) -> String {
+        match self.info(asset_id) {
+            Some(info) => format!(
+                "{} {} ({amount})",
+                format_amount(amount, info.decimals),
+                info.symbol
+            ),
+            None => format!("{amount} of {asset_id}"),
+        }
+    }
+
+    pub fn save(&self) {
+        let file = AssetsFile {
+            assets: self
+                .assets
+                .iter()
+                .map(|asset| AssetEntry {
+                    symbol: asset.symbol.clone(),
+                    asset_id: asset.asset_id.to_string(),
+                    decimals: asset.decimals,
+                })
+                .collect(),
+        };
+
+        let contents =
+            serde_json::to_string_pretty(&file).expect("should be able to encode asset registry");
+        std::fs::write(&self.path, contents).expect("should be able to write asset registry");
+    }
+}
+
+impl Default for AssetRegistry {
+    fn default() -> Self {
+        Self::load(DEFAULT_ASSETS_PATH)
+    }
+}
+
+/// Whole units with up to `decimals` decimal places to base units.
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u64, String> {
+    let decimals = decimals as usize;
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+
+    if fraction.len() > decimals {
+        return Err(format!("{amount} has more than {decimals} decimals"));
+    }
+
+    let digits = format!("{whole}{fraction:0<decimals$}");
+    if whole.is_empty() && fraction.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid amount {amount}"));
+    }
+
+    digits.parse().map_err(|_| format!("{amount} is too large"))
+}
+
+/// A balance as the provider returns it, keyed by hex asset ID, ordered by asset.
+pub fn sorted_balance(balance: HashMap<String, u64>) -> Vec<(AssetId, u64)> {
+    let mut balance = balance
+        .into_iter()
+        .map(|(asset_id, amount)| {
+            let asset_id =
+                AssetId::from_str(&asset_id).expect("provider should return valid asset ids");
+            (asset_id, amount)
+        })
+        .collect::<Vec<_>>();
+    balance.sort();
+    balance
+}
+
+/// Base units to whole units, without trailing zeros.
+pub fn format_amount(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let digits = format!("{amount:0>width$}", width = decimals + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
@@ -0,0 +1,353 @@
+//! Many operations from one file.
+//!
+//! A batch file is either a JSON array of rows or a CSV file with a header line.
+//! Every row has an `op` (`fund`, `send-to`, `claim`, `disprove` or `fulfill`)
+//! and the fields that operation needs:
+//!
+//! ```text
+//! op,recipient,owner,asset_id,amount,claim_id,contract_id
+//! fund,,,,10000,,
+//! send-to,fuel1...,,,500,,
+//! disprove,,,,,3,
+//! ```
+//!
+//! Recipients and owners may be given in any form [`crate::address`] accepts,
+//! including names from the address book.
+//!
+//! All rows are validated before anything is submitted. Disprove and fulfill use
+//! the active contract unless the row has a `contract_id`.
+//!
+//! Operations run concurrently, all from the same wallet. Concurrent transactions
+//! may pick the same coins, in which case all but one are rejected and retried.
+//! Only operations that never made it into a block are retried. A transaction that
+//! reverted, or wasn't seen to be included in time, is reported as it is, since
+//! running it again could do the same thing twice.
+
+use std::{path::Path, str::FromStr, sync::Arc, time::Duration};
+
+use fuels::{prelude::*, types::Identity};
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    address::{self, AddressBook},
+    error::TxError,
+    outcome::OperationOutcome,
+    Kpop, COMMIT_TIMEOUT,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Row {
+    pub op: String,
+    #[serde(default)]
+    pub recipient: Option<String>,
+    #[serde(default)]
+    pub owner: Option<String>,
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub amount: Option<u64>,
+    #[serde(default)]
+    pub claim_id: Option<u64>,
+    #[serde(default)]
+    pub contract_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operation {
+    Fund {
+        asset_id: Option<AssetId>,
+        amount: u64,
+    },
+    SendTo {
+        recipient: Identity,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    },
+    Claim {
+        owner: Address,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    },
+    Disprove {
+        contract_id: Option<ContractId>,
+        claim_id: u64,
+    },
+    Fulfill {
+        contract_id: Option<ContractId>,
+        claim_id: u64,
+    },
+}
+
+impl Operation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Operation::Fund { .. } => "fund",
+            Operation::SendTo { .. } => "send-to",
+            Operation::Claim { .. } => "claim",
+            Operation::Disprove { .. } => "disprove",
+            Operation::Fulfill { .. } => "fulfill",
+        }
+    }
+
+    async fn run(self, kp: &Kpop) -> std::result::Result<OperationOutcome, TxError> {
+        match self {
+            Operation::Fund { asset_id, amount } => {
+                let txid = kp.fund_predicate_no_wait(asset_id, amount).await?;
+                kp.await_tx(txid, COMMIT_TIMEOUT).await
+            }
+            Operation::SendTo {
+                recipient,
+                asset_id,
+                amount,
+            } => {
+                let txid = kp.send_to_no_wait(&recipient, asset_id, amount).await?;
+                kp.await_tx(txid, COMMIT_TIMEOUT).await
+            }
+            Operation::Claim {
+                owner,
+                asset_id,
+                amount,
+            } => kp.claim(owner, asset_id, amount).await,
+            Operation::Disprove {
+                contract_id,
+                claim_id,
+            } => {
+                kp.disprove_claim_in(contract_id.unwrap_or(kp.contract_id), claim_id)
+                    .await
+            }
+            Operation::Fulfill {
+                contract_id,
+                claim_id,
+            } => {
+                kp.fulfill_claim_in(contract_id.unwrap_or(kp.contract_id), claim_id)
+                    .await
+            }
+        }
+    }
+}
+
+impl Row {
+    pub fn validate(&self, book: &AddressBook) -> std::result::Result<Operation, String> {
+        let asset_id = self
+            .asset_id
+            .as_deref()
+            .map(|s| AssetId::from_str(s).map_err(|_| format!("invalid asset_id {s}")))
+            .transpose()?;
+        let contract_id = self
+            .contract_id
+            .as_deref()
+            .map(|s| ContractId::from_str(s).map_err(|_| format!("invalid contract_id {s}")))
+            .transpose()?;
+        let amount = || {
+            self.amount
+                .ok_or_else(|| format!("{} needs an amount", self.op))
+        };
+        let claim_id = || {
+            self.claim_id
+                .ok_or_else(|| format!("{} needs a claim_id", self.op))
+        };
+        let identity = |field: &str, value: &Option<String>| {
+            let value = value
+                .as_deref()
+                .ok_or_else(|| format!("{} needs a {field}", self.op))?;
+            address::parse_identity(value, book).map_err(|err| format!("{field}: {err}"))
+        };
+
+        match self.op.as_str() {
+            "fund" => Ok(Operation::Fund {
+                asset_id,
+                amount: amount()?,
+            }),
+            "send-to" => Ok(Operation::SendTo {
+                recipient: identity("recipient", &self.recipient)?,
+                asset_id,
+                amount: amount()?,
+            }),
+            "claim" => Ok(Operation::Claim {
+                owner: match identity("owner", &self.owner)? {
+                    Identity::Address(owner) => owner,
+                    Identity::ContractId(_) => return Err("owner should be an address".to_string()),
+                },
+                asset_id,
+                amount: amount()?,
+            }),
+            "disprove" => Ok(Operation::Disprove {
+                contract_id,
+                claim_id: claim_id()?,
+            }),
+            "fulfill" => Ok(Operation::Fulfill {
+                contract_id,
+                claim_id: claim_id()?,
+            }),
+            op => Err(format!("unknown op {op}")),
+        }
+    }
+}
+
+/// Load rows from a `.csv` file, or a JSON file otherwise.
+pub fn load(path: impl AsRef<Path>) -> Vec<Row> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path).expect("should be able to read batch file");
+
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        parse_csv(&contents).expect("batch file should be valid csv")
+    } else {
+        parse_json(&contents).expect("batch file should be valid json")
+    }
+}
+
+pub fn parse_json(contents: &str) -> serde_json::Result<Vec<Row>> {
+    serde_json::from_str(contents)
+}
+
+pub fn parse_csv(contents: &str) -> csv::Result<Vec<Row>> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(contents.as_bytes())
+        .deserialize()
+        .collect()
+}
+
+/// Validate every row, returning the operations or every invalid row with its
+/// 1-based row number.
+pub fn validate(
+    rows: &[Row],
+    book: &AddressBook,
+) -> std::result::Result<Vec<Operation>, Vec<(usize, String)>> {
+    let mut operations = Vec::new();
+    let mut errors = Vec::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        match row.validate(book) {
+            Ok(operation) => operations.push(operation),
+            Err(err) => errors.push((i + 1, err)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(operations)
+    } else {
+        Err(errors)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BatchOptions {
+    pub concurrency: usize,
+    /// Attempts after the first one for operations that fail before their
+    /// transaction is submitted, or whose transaction is squeezed out.
+    pub retries: u32,
+    /// Wait before the first retry, doubled for every further one.
+    pub retry_delay: Duration,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 1,
+            retries: 2,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowResult {
+    pub row: usize,
+    pub op: String,
+    pub attempts: u32,
+    pub tx_id: Option<String>,
+    pub claim_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl RowResult {
+    pub fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Run `operations`, returning one result per operation in the same order.
+pub async fn execute(
+    kp: &Kpop,
+    operations: Vec<Operation>,
+    options: BatchOptions,
+) -> Vec<RowResult> {
+    let kp = Arc::new(kp.clone());
+
+    let mut results = stream::iter(operations.into_iter().enumerate())
+        .map(|(i, operation)| execute_row(kp.clone(), i + 1, operation, options))
+        .buffer_unordered(options.concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    results.sort_by_key(|result| result.row);
+    results
+}
+
+async fn execute_row(
+    kp: Arc<Kpop>,
+    row: usize,
+    operation: Operation,
+    options: BatchOptions,
+) -> RowResult {
+    let mut delay = options.retry_delay;
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        match operation.clone().run(&kp).await {
+            Ok(outcome) => {
+                return RowResult {
+                    row,
+                    op: operation.name().to_string(),
+                    attempts,
+                    tx_id: Some(outcome.tx_id.to_string()),
+                    claim_id: outcome.claim_ids.first().copied(),
+                    error: None,
+                }
+            }
+            // None of these left anything on chain, so running again can't do the
+            // same thing twice.
+            Err(
+                TxError::NotSubmitted { .. }
+                | TxError::Rejected { .. }
+                | TxError::SqueezedOut { .. },
+            ) if attempts <= options.retries => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            Err(err) => {
+                return RowResult {
+                    row,
+                    op: operation.name().to_string(),
+                    attempts,
+                    tx_id: err.tx_id().map(|tx_id| tx_id.to_string()),
+                    claim_id: None,
+                    error: Some(err.to_string()),
+                }
+            }
+        }
+    }
+}
+
+/// Write results as CSV if `path` ends in `.csv`, as JSON otherwise.
+pub fn write_report(path: impl AsRef<Path>, results: &[RowResult]) {
+    let path = path.as_ref();
+
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        let mut writer = csv::Writer::from_path(path).expect("should be able to create report");
+        for result in results {
+            writer
+                .serialize(result)
+                .expect("should be able to write report");
+        }
+        writer.flush().expect("should be able to write report");
+    } else {
+        let contents =
+            serde_json::to_string_pretty(results).expect("should be able to encode report");
+        std::fs::write(path, contents).expect("should be able to write report");
+    }
+}
@@ -0,0 +1,38 @@
+//! Claim helpers shared by the CLI, dashboard and devnet, and the serializable
+//! view of claims for the API and event stream.
+
+use fuels::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{Claim, CHALLENGE_PERIOD};
+
+/// Blocks to produce at `height` before `claim` can be fulfilled. A transaction
+/// lands in the next block, so 0 means one submitted now is in time.
+pub fn blocks_left(claim: &Claim, height: u32) -> u32 {
+    (claim.block_height + CHALLENGE_PERIOD).saturating_sub(height + 1)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClaimView {
+    pub contract_id: String,
+    pub id: u64,
+    pub owner: String,
+    pub recipient: String,
+    pub asset_id: String,
+    pub amount: u64,
+    pub block_height: u32,
+}
+
+impl ClaimView {
+    pub fn new(contract_id: ContractId, claim: Claim) -> Self {
+        Self {
+            contract_id: contract_id.to_string(),
+            id: claim.id,
+            owner: Bech32Address::from(claim.owner).to_string(),
+            recipient: Bech32Address::from(claim.recipient).to_string(),
+            asset_id: claim.asset.to_string(),
+            amount: claim.amount,
+            block_height: claim.block_height,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! Local network for trying kpop out.
+//!
+//! The node runs in-process with debug mode on, so blocks can be produced on
+//! demand to get past the challenge period. The same wallets the test harnesses
+//! use are funded with the base asset, and the first of them deploys the claims
+//! contract.
+
+use std::{net::SocketAddr, str::FromStr};
+
+use fuels::{crypto::SecretKey, prelude::*};
+
+use crate::{profile::Profile, Kpop};
+
+pub const DEFAULT_DEVNET_ADDR: &str = "127.0.0.1:4000";
+pub const DEFAULT_PROFILE_PATH: &str = "./devnet.json";
+
+pub use kpop_core::PREFUNDED_KEYS;
+pub const COINS_PER_WALLET: u64 = 10;
+pub const AMOUNT_PER_COIN: u64 = 1_000_000_000;
+
+pub struct Devnet {
+    pub provider: Provider,
+    /// Funded wallets, in the order of [`PREFUNDED_KEYS`].
+    pub wallets: Vec<WalletUnlocked>,
+    /// Acting as the first wallet, with the freshly deployed contract.
+    pub kpop: Kpop,
+}
+
+impl Devnet {
+    /// Start the node listening on `addr`. It stops when the runtime does.
+    pub async fn launch(addr: SocketAddr) -> Self {
+        let keys = PREFUNDED_KEYS
+            .map(|key| SecretKey::from_str(key).expect("prefunded keys should be valid"));
+
+        let coins = keys
+            .iter()
+            .flat_map(|key| {
+                let wallet = WalletUnlocked::new_from_private_key(*key, None);
+                setup_single_asset_coins(
+                    wallet.address(),
+                    AssetId::zeroed(),
+                    COINS_PER_WALLET,
+                    AMOUNT_PER_COIN,
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let node_config = NodeConfig {
+            addr,
+            debug: true,
+            ..NodeConfig::default()
+        };
+        let provider = setup_test_provider(coins, vec![], Some(node_config), None)
+            .await
+            .expect("should be able to start node");
+
+        let wallets = keys
+            .iter()
+            .map(|key| WalletUnlocked::new_from_private_key(*key, Some(provider.clone())))
+            .collect();
+        let kpop = Kpop::deploy(provider.clone(), keys[0]).await;
+
+        Self {
+            provider,
+            wallets,
+            kpop,
+        }
+    }
+
+    /// Connects to the node as the first wallet, using the deployed contract.
+    pub fn profile(&self) -> Profile {
+        Profile {
+            provider_url: Some(self.provider.url().to_string()),
+            private_key: Some(PREFUNDED_KEYS[0].to_string()),
+            contract_id: Some(self.kpop.contract_id.to_string()),
+        }
+    }
+}
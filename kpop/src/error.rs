@@ -0,0 +1,245 @@
+//! Failures of submitted and rejected transactions, of signing transactions
+//! offline, and of loading artifacts.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use fuels::{
+    tx::TxId,
+    types::{Address, ContractId},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxError {
+    /// Couldn't be built, e.g. because there aren't enough coins to spend or the
+    /// node couldn't be reached. Nothing was submitted.
+    NotSubmitted { reason: String },
+    /// Not accepted by the node, e.g. because it failed validation. Nothing was
+    /// submitted.
+    Rejected { tx_id: TxId, reason: String },
+    /// Dropped from the pool without being included, e.g. because its inputs were
+    /// spent by another transaction or it ran out of fee.
+    SqueezedOut { tx_id: TxId, reason: String },
+    /// Included in a block but reverted.
+    Reverted { tx_id: TxId, reason: String },
+    /// Not included before the timeout. It may still be, check again later.
+    Timeout { tx_id: TxId },
+}
+
+impl TxError {
+    pub(crate) fn not_submitted(err: impl fmt::Display) -> Self {
+        TxError::NotSubmitted {
+            reason: err.to_string(),
+        }
+    }
+
+    /// `None` if the transaction was never built.
+    pub fn tx_id(&self) -> Option<TxId> {
+        match self {
+            TxError::NotSubmitted { .. } => None,
+            TxError::Rejected { tx_id, .. }
+            | TxError::SqueezedOut { tx_id, .. }
+            | TxError::Reverted { tx_id, .. }
+            | TxError::Timeout { tx_id } => Some(*tx_id),
+        }
+    }
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxError::NotSubmitted { reason } => {
+                write!(f, "transaction was not submitted: {reason}")
+            }
+            TxError::Rejected { tx_id, reason } => {
+                write!(f, "transaction {tx_id} was rejected: {reason}")
+            }
+            TxError::SqueezedOut { tx_id, reason } => {
+                write!(f, "transaction {tx_id} was squeezed out: {reason}")
+            }
+            TxError::Reverted { tx_id, reason } => {
+                write!(f, "transaction {tx_id} reverted: {reason}")
+            }
+            TxError::Timeout { tx_id } => {
+                write!(f, "transaction {tx_id} was not included in time")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TxError {}
+
+/// Claims that would make a combined transaction fail, with the reason for each.
+///
+/// Nothing is submitted when any claim is rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedClaims(pub Vec<(ContractId, u64, String)>);
+
+impl fmt::Display for RejectedClaims {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nothing was submitted, rejected claims:")?;
+        for (contract_id, claim_id, reason) in &self.0 {
+            write!(f, "\n  {claim_id} in {contract_id}: {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for RejectedClaims {}
+
+/// Failure to disprove several claims in one transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DisproveManyError {
+    /// Some claims would fail, so nothing was submitted.
+    Rejected(RejectedClaims),
+    /// The combined transaction failed, e.g. because a claim was resolved after
+    /// it had been checked.
+    Tx(TxError),
+}
+
+impl From<RejectedClaims> for DisproveManyError {
+    fn from(err: RejectedClaims) -> Self {
+        DisproveManyError::Rejected(err)
+    }
+}
+
+impl From<TxError> for DisproveManyError {
+    fn from(err: TxError) -> Self {
+        DisproveManyError::Tx(err)
+    }
+}
+
+impl fmt::Display for DisproveManyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisproveManyError::Rejected(err) => err.fmt(f),
+            DisproveManyError::Tx(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DisproveManyError {}
+
+/// Failure to read, sign or write an [`crate::unsigned::UnsignedTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsignedTxError {
+    Unreadable {
+        path: PathBuf,
+        reason: String,
+    },
+    Unwritable {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The file isn't an unsigned transaction.
+    Invalid {
+        path: PathBuf,
+        reason: String,
+    },
+    /// The key doesn't belong to the predicate owner.
+    WrongSigner {
+        owner: Address,
+        signer: Address,
+    },
+    /// The transaction was changed after its ID was computed.
+    IdMismatch {
+        tx_id: TxId,
+        actual: TxId,
+    },
+}
+
+impl fmt::Display for UnsignedTxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsignedTxError::Unreadable { path, reason } => {
+                write!(f, "can't read {}: {reason}", path.display())
+            }
+            UnsignedTxError::Unwritable { path, reason } => {
+                write!(f, "can't write {}: {reason}", path.display())
+            }
+            UnsignedTxError::Invalid { path, reason } => {
+                write!(f, "{} is not a valid transaction: {reason}", path.display())
+            }
+            UnsignedTxError::WrongSigner { owner, signer } => write!(
+                f,
+                "transaction must be signed by the predicate owner {owner}, not {signer}"
+            ),
+            UnsignedTxError::IdMismatch { tx_id, actual } => {
+                write!(
+                    f,
+                    "transaction doesn't match its ID {tx_id}, it is {actual}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for UnsignedTxError {}
+
+/// Pinned artifacts that can't be trusted or read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactError {
+    Unreadable {
+        path: PathBuf,
+        reason: String,
+    },
+    InvalidManifest(String),
+    /// The manifest's current version isn't one of its pinned versions.
+    UnknownCurrent(String),
+    /// A pinned file changed after it was pinned.
+    HashMismatch {
+        version: String,
+        path: PathBuf,
+    },
+    AlreadyPinned(String),
+    Unwritable {
+        path: PathBuf,
+        reason: String,
+    },
+}
+
+impl ArtifactError {
+    pub(crate) fn unreadable(path: &Path, err: std::io::Error) -> Self {
+        ArtifactError::Unreadable {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        }
+    }
+
+    pub(crate) fn unwritable(path: &Path, err: std::io::Error) -> Self {
+        ArtifactError::Unwritable {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArtifactError::Unreadable { path, reason } => {
+                write!(f, "can't read {}: {reason}", path.display())
+            }
+            ArtifactError::InvalidManifest(reason) => {
+                write!(f, "invalid artifacts manifest: {reason}")
+            }
+            ArtifactError::UnknownCurrent(name) => {
+                write!(f, "current version {name} is not pinned in the manifest")
+            }
+            ArtifactError::HashMismatch { version, path } => write!(
+                f,
+                "{} of version {version} does not match its pinned hash",
+                path.display()
+            ),
+            ArtifactError::AlreadyPinned(name) => write!(f, "version {name} is already pinned"),
+            ArtifactError::Unwritable { path, reason } => {
+                write!(f, "can't write {}: {reason}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ArtifactError {}
@@ -0,0 +1,64 @@
+//! Simulated outcome of a transaction that is never submitted.
+
+use fuels::{
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Input, Output},
+    types::{errors::transaction::Reason, transaction::Transaction, tx_status::TxStatus},
+};
+
+use crate::error::TxError;
+
+#[derive(Debug, Clone)]
+pub struct Estimate {
+    pub gas_used: u64,
+    pub total_fee: u64,
+    pub inputs: Vec<Input>,
+    pub outputs: Vec<Output>,
+    pub revert_reason: Option<String>,
+}
+
+impl Estimate {
+    /// Dry run `tx` against the current chain state.
+    ///
+    /// Revert reasons are decoded with `log_decoder` when one is given, so contract
+    /// errors show up as e.g. `TooSoon(112)` rather than a raw revert code.
+    pub(crate) async fn dry_run(
+        provider: &Provider,
+        tx: ScriptTransaction,
+        log_decoder: Option<&LogDecoder>,
+    ) -> std::result::Result<Self, TxError> {
+        let inputs = tx.inputs().clone();
+        let outputs = tx.outputs().clone();
+
+        let status = provider.dry_run(tx).await.map_err(TxError::not_submitted)?;
+
+        let (gas_used, total_fee) = match &status {
+            TxStatus::Success {
+                total_gas,
+                total_fee,
+                ..
+            }
+            | TxStatus::Revert {
+                total_gas,
+                total_fee,
+                ..
+            } => (*total_gas, *total_fee),
+            _ => (0, 0),
+        };
+
+        let revert_reason = match status.take_receipts_checked(log_decoder) {
+            Ok(_) => None,
+            Err(Error::Transaction(Reason::Reverted { reason, .. })) => Some(reason),
+            Err(err) => Some(err.to_string()),
+        };
+
+        Ok(Self {
+            gas_used,
+            total_fee,
+            inputs,
+            outputs,
+            revert_reason,
+        })
+    }
+}
@@ -0,0 +1,54 @@
+//! Gas limits and user supplied transaction policies.
+
+use fuels::{prelude::*, tx::Receipt};
+
+/// Estimated script gas is scaled by this before being used as a limit, since
+/// chain state may change between estimation and inclusion.
+pub const SAFETY_MULTIPLIER: f64 = 1.2;
+
+/// User overrides for the policies of every submitted transaction.
+///
+/// A `gas_limit` replaces the estimated script gas limit altogether.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GasOptions {
+    pub gas_limit: Option<u64>,
+    pub tip: Option<u64>,
+    pub max_fee: Option<u64>,
+}
+
+impl GasOptions {
+    pub fn tx_policies(&self) -> TxPolicies {
+        let mut policies = TxPolicies::default();
+
+        if let Some(gas_limit) = self.gas_limit {
+            policies = policies.with_script_gas_limit(gas_limit);
+        }
+
+        if let Some(tip) = self.tip {
+            policies = policies.with_tip(tip);
+        }
+
+        if let Some(max_fee) = self.max_fee {
+            policies = policies.with_max_fee(max_fee);
+        }
+
+        policies
+    }
+
+    /// Policies for a script that used `script_gas` during estimation.
+    pub fn tx_policies_for(&self, script_gas: u64) -> TxPolicies {
+        match self.gas_limit {
+            Some(_) => self.tx_policies(),
+            None => self
+                .tx_policies()
+                .with_script_gas_limit((script_gas as f64 * SAFETY_MULTIPLIER).ceil() as u64),
+        }
+    }
+}
+
+pub(crate) fn script_gas_used(receipts: &[Receipt]) -> Option<u64> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+        _ => None,
+    })
+}
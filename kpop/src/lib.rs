@@ -1,11 +1,68 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr, time::Duration};
 
-use fuels::{crypto::SecretKey, prelude::*, tx::TxId, types::Bits256};
+use fuels::{
+    core::{codec::LogDecoder, Configurables},
+    crypto::SecretKey,
+    prelude::*,
+    programs::calls::{CallHandler, ContractCall, ScriptCall},
+    tx::{Bytes32, Output, TxId, TxPointer, UtxoId},
+    types::{
+        input::Input, transaction_builders::ScriptBuildStrategy, tx_status::TxStatus, Identity,
+    },
+};
+
+pub mod address;
+pub mod artifacts;
+pub mod assets;
+pub mod batch;
+pub mod claims;
+#[cfg(feature = "devnet")]
+pub mod devnet;
+pub mod error;
+pub mod estimate;
+pub mod gas;
+pub mod node;
+pub mod notify;
+pub mod outcome;
+pub mod profile;
+pub mod registry;
+pub mod server;
+pub mod tui;
+pub mod unsigned;
+pub mod watch;
+
+use address::AddressBook;
+use artifacts::{ArtifactVersion, Artifacts};
+use assets::AssetRegistry;
+use error::{DisproveManyError, RejectedClaims, TxError};
+use estimate::Estimate;
+use gas::GasOptions;
+use outcome::OperationOutcome;
+use registry::Registry;
+use unsigned::UnsignedTransaction;
+
+pub use claims_contract::Claim;
+pub use kpop_core::CHALLENGE_PERIOD;
+
+/// Gas forwarded from the make-claim script to `initiate_claim`. The VM caps it
+/// at whatever is left of the script gas limit.
+const CLAIM_FORWARDED_GAS: u64 = 10_000_000;
+
+/// How often [`Kpop::await_tx`] asks the node about a transaction.
+const AWAIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How long calls that wait for their transaction give it to be included.
+pub const COMMIT_TIMEOUT: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Clone)]
 pub struct Kpop {
     pub wallet: WalletUnlocked,
     pub contract_id: ContractId,
+    pub artifacts: Artifacts,
+    pub registry: Registry,
+    pub assets: AssetRegistry,
+    pub address_book: AddressBook,
+    pub gas: GasOptions,
 }
 
 impl Kpop {
@@ -25,6 +82,11 @@ impl Kpop {
         Self {
             wallet,
             contract_id,
+            artifacts: Artifacts::dev(),
+            registry: Registry::default(),
+            assets: AssetRegistry::default(),
+            address_book: AddressBook::default(),
+            gas: GasOptions::default(),
         }
     }
 
@@ -34,37 +96,174 @@ impl Kpop {
         Self {
             wallet,
             contract_id,
+            artifacts: Artifacts::dev(),
+            registry: Registry::default(),
+            assets: AssetRegistry::default(),
+            address_book: AddressBook::default(),
+            gas: GasOptions::default(),
         }
     }
 
+    pub fn with_artifacts(self, artifacts: Artifacts) -> Self {
+        Self { artifacts, ..self }
+    }
+
+    pub fn with_registry(self, registry: Registry) -> Self {
+        Self { registry, ..self }
+    }
+
+    pub fn with_assets(self, assets: AssetRegistry) -> Self {
+        Self { assets, ..self }
+    }
+
+    pub fn with_address_book(self, address_book: AddressBook) -> Self {
+        Self {
+            address_book,
+            ..self
+        }
+    }
+
+    pub fn with_gas_options(self, gas: GasOptions) -> Self {
+        Self { gas, ..self }
+    }
+
+    /// The active contract followed by every other contract in the registry.
+    pub fn contract_ids(&self) -> Vec<ContractId> {
+        let mut contract_ids = vec![self.contract_id];
+
+        for contract_id in self.registry.contracts() {
+            if !contract_ids.contains(contract_id) {
+                contract_ids.push(*contract_id);
+            }
+        }
+
+        contract_ids
+    }
+
     pub async fn wallet_balance(&self) -> HashMap<String, u64> {
+        self.try_wallet_balance()
+            .await
+            .expect("should be able to get balances")
+    }
+
+    /// Like [`Kpop::wallet_balance`], but node errors are returned rather than
+    /// panicking.
+    pub(crate) async fn try_wallet_balance(&self) -> Result<HashMap<String, u64>> {
         self.wallet
             .provider()
             .unwrap()
             .get_balances(&self.wallet.address().into())
             .await
-            .expect("should be able to get balances")
     }
 
     pub async fn predicate_balance(&self) -> HashMap<String, u64> {
+        self.predicate_balance_of(self.wallet.address().into())
+            .await
+    }
+
+    pub async fn predicate_balance_of(&self, owner: Address) -> HashMap<String, u64> {
+        self.try_predicate_balance_of(owner)
+            .await
+            .expect("should be able to get balances")
+    }
+
+    /// Like [`Kpop::predicate_balance_of`], but node errors are returned rather than
+    /// panicking.
+    pub(crate) async fn try_predicate_balance_of(
+        &self,
+        owner: Address,
+    ) -> Result<HashMap<String, u64>> {
         self.wallet
             .provider()
             .unwrap()
-            .get_balances(&self.predicate_address().await.into())
+            .get_balances(self.predicate(owner).await.address())
             .await
-            .expect("should be able to get balances")
     }
 
     pub async fn get_claims(&self) -> Vec<claims_contract::Claim> {
-        self.contract_instance()
+        self.get_claims_in(self.contract_id).await
+    }
+
+    /// Claims against the owner in every known contract.
+    pub async fn get_all_claims(&self) -> Vec<(ContractId, claims_contract::Claim)> {
+        self.get_all_claims_of(self.wallet.address().into()).await
+    }
+
+    pub async fn get_all_claims_of(
+        &self,
+        owner: Address,
+    ) -> Vec<(ContractId, claims_contract::Claim)> {
+        self.try_get_all_claims_of(owner)
+            .await
+            .expect("should be able to get claims")
+    }
+
+    /// Like [`Kpop::get_all_claims_of`], but node errors are returned rather than
+    /// panicking.
+    pub(crate) async fn try_get_all_claims_of(
+        &self,
+        owner: Address,
+    ) -> Result<Vec<(ContractId, claims_contract::Claim)>> {
+        let mut claims = Vec::new();
+
+        for contract_id in self.contract_ids() {
+            for claim in self.try_get_claims_of(contract_id, owner).await? {
+                claims.push((contract_id, claim));
+            }
+        }
+
+        Ok(claims)
+    }
+
+    /// Contracts holding a claim against the owner with the given ID.
+    ///
+    /// Claim IDs are only unique within a contract, so there may be more than one.
+    pub async fn locate_claim(&self, claim_id: u64) -> Vec<ContractId> {
+        self.locate_claim_of(self.wallet.address().into(), claim_id)
+            .await
+    }
+
+    /// Like [`Kpop::locate_claim`], for claims against `owner`.
+    pub async fn locate_claim_of(&self, owner: Address, claim_id: u64) -> Vec<ContractId> {
+        self.get_all_claims_of(owner)
+            .await
+            .into_iter()
+            .filter(|(_, claim)| claim.id == claim_id)
+            .map(|(contract_id, _)| contract_id)
+            .collect()
+    }
+
+    pub async fn get_claims_in(&self, contract_id: ContractId) -> Vec<claims_contract::Claim> {
+        self.get_claims_of(contract_id, self.wallet.address().into())
+            .await
+    }
+
+    pub async fn get_claims_of(
+        &self,
+        contract_id: ContractId,
+        owner: Address,
+    ) -> Vec<claims_contract::Claim> {
+        self.try_get_claims_of(contract_id, owner)
+            .await
+            .expect("should be able to get claims")
+    }
+
+    /// Like [`Kpop::get_claims_of`], but node errors are returned rather than
+    /// panicking. Only simulated, so the gas limit isn't estimated.
+    pub(crate) async fn try_get_claims_of(
+        &self,
+        contract_id: ContractId,
+        owner: Address,
+    ) -> Result<Vec<claims_contract::Claim>> {
+        Ok(self
+            .contract_instance_at(contract_id)
             .await
             .methods()
-            .get_claims(&self.wallet.address().into())
-            .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000))
+            .get_claims(owner)
+            .with_tx_policies(self.gas.tx_policies())
             .simulate(Execution::Realistic)
-            .await
-            .unwrap()
-            .value
+            .await?
+            .value)
     }
 
     pub async fn predicate_address(&self) -> Bech32Address {
@@ -74,192 +273,971 @@ impl Kpop {
             .clone()
     }
 
-    pub async fn fund_predicate(&self, asset_id: Option<AssetId>, amount: u64) -> TxId {
-        let asset_id =
-            asset_id.unwrap_or_else(|| self.wallet.provider().unwrap().base_asset_id().clone());
+    /// Predicate addresses of the owner for every known artifact version.
+    pub async fn predicate_addresses(&self) -> Vec<(ArtifactVersion, Bech32Address)> {
+        let mut addresses = Vec::new();
 
-        let (txid, _) = self
-            .wallet
-            .transfer(
-                &self.predicate_address().await,
-                amount,
-                asset_id,
-                TxPolicies::default(),
-            )
+        for version in self.artifacts.versions() {
+            let address = self
+                .versioned_predicate(&version, self.wallet.address().into())
+                .await
+                .address()
+                .clone();
+            addresses.push((version, address));
+        }
+
+        addresses
+    }
+
+    /// Balances left at predicate addresses of previous artifact versions.
+    pub async fn stranded_balances(&self) -> Vec<(ArtifactVersion, HashMap<String, u64>)> {
+        self.try_stranded_balances()
             .await
-            .expect("should be able to fund predicate");
+            .expect("should be able to get balances")
+    }
+
+    /// Like [`Kpop::stranded_balances`], but node errors are returned rather than
+    /// panicking.
+    pub(crate) async fn try_stranded_balances(
+        &self,
+    ) -> Result<Vec<(ArtifactVersion, HashMap<String, u64>)>> {
+        let mut stranded = Vec::new();
 
-        txid
+        for version in self.artifacts.previous() {
+            let predicate = self
+                .versioned_predicate(&version, self.wallet.address().into())
+                .await;
+            let balance = self
+                .wallet
+                .provider()
+                .unwrap()
+                .get_balances(predicate.address())
+                .await?;
+
+            if !balance.is_empty() {
+                stranded.push((version, balance));
+            }
+        }
+
+        Ok(stranded)
+    }
+
+    /// Move all funds at previous-version predicate addresses to the current one.
+    ///
+    /// Every asset is moved in a transaction of its own. When one fails, the ones
+    /// before it have gone through and migrating again moves what's left.
+    pub async fn migrate(&self) -> std::result::Result<Vec<OperationOutcome>, TxError> {
+        let target = self.predicate_address().await;
+        let mut outcomes = Vec::new();
+
+        for (predicate, asset_id, amount) in self.stranded_coins().await? {
+            let tx = self
+                .spend_predicate_tx(&predicate, &target, asset_id, amount)
+                .await?;
+            outcomes.push(self.submit_and_await(tx).await?);
+        }
+
+        Ok(outcomes)
+    }
+
+    pub async fn migrate_no_wait(&self) -> std::result::Result<Vec<TxId>, TxError> {
+        let target = self.predicate_address().await;
+        let mut txids = Vec::new();
+
+        for (predicate, asset_id, amount) in self.stranded_coins().await? {
+            let tx = self
+                .spend_predicate_tx(&predicate, &target, asset_id, amount)
+                .await?;
+            txids.push(self.submit_no_wait(tx).await?);
+        }
+
+        Ok(txids)
+    }
+
+    pub async fn estimate_migrate(&self) -> std::result::Result<Vec<Estimate>, TxError> {
+        let target = self.predicate_address().await;
+        let mut estimates = Vec::new();
+
+        for (predicate, asset_id, amount) in self.stranded_coins().await? {
+            let tx = self
+                .spend_predicate_tx(&predicate, &target, asset_id, amount)
+                .await?;
+            estimates.push(self.dry_run(tx, None).await?);
+        }
+
+        Ok(estimates)
+    }
+
+    async fn stranded_coins(&self) -> std::result::Result<Vec<(Predicate, AssetId, u64)>, TxError> {
+        let mut coins = Vec::new();
+
+        let stranded = self
+            .try_stranded_balances()
+            .await
+            .map_err(TxError::not_submitted)?;
+        for (version, balance) in stranded {
+            let predicate = self
+                .versioned_predicate(&version, self.wallet.address().into())
+                .await;
+
+            for (asset_id, amount) in balance {
+                let asset_id =
+                    AssetId::from_str(&asset_id).expect("provider should return valid asset ids");
+                coins.push((predicate.clone(), asset_id, amount));
+            }
+        }
+
+        Ok(coins)
+    }
+
+    pub async fn fund_predicate(
+        &self,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let tx = self
+            .fund_predicate_tx(&[self.asset_amount(asset_id, amount)])
+            .await?;
+        self.submit_and_await(tx).await
+    }
+
+    pub async fn fund_predicate_no_wait(
+        &self,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<TxId, TxError> {
+        let tx = self
+            .fund_predicate_tx(&[self.asset_amount(asset_id, amount)])
+            .await?;
+        self.submit_no_wait(tx).await
+    }
+
+    pub async fn estimate_fund_predicate(
+        &self,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        let tx = self
+            .fund_predicate_tx(&[self.asset_amount(asset_id, amount)])
+            .await?;
+        self.dry_run(tx, None).await
+    }
+
+    /// Move several assets into the predicate in one transaction.
+    pub async fn fund_predicate_many(
+        &self,
+        amounts: &[(AssetId, u64)],
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let tx = self.fund_predicate_tx(amounts).await?;
+        self.submit_and_await(tx).await
+    }
+
+    pub async fn fund_predicate_many_no_wait(
+        &self,
+        amounts: &[(AssetId, u64)],
+    ) -> std::result::Result<TxId, TxError> {
+        let tx = self.fund_predicate_tx(amounts).await?;
+        self.submit_no_wait(tx).await
+    }
+
+    pub async fn estimate_fund_predicate_many(
+        &self,
+        amounts: &[(AssetId, u64)],
+    ) -> std::result::Result<Estimate, TxError> {
+        let tx = self.fund_predicate_tx(amounts).await?;
+        self.dry_run(tx, None).await
+    }
+
+    /// Everything in the wallet, except enough of the base asset to pay for moving
+    /// it into the predicate.
+    pub async fn all_except_gas(&self) -> std::result::Result<Vec<(AssetId, u64)>, TxError> {
+        let base_asset_id = *self.wallet.provider().unwrap().base_asset_id();
+
+        let mut amounts = self
+            .try_wallet_balance()
+            .await
+            .map_err(TxError::not_submitted)?
+            .into_iter()
+            .map(|(asset_id, amount)| {
+                let asset_id =
+                    AssetId::from_str(&asset_id).expect("provider should return valid asset ids");
+                (asset_id, amount)
+            })
+            .filter(|(_, amount)| *amount > 0)
+            .collect::<Vec<_>>();
+        amounts.sort();
+
+        let Some(balance) = amounts
+            .iter()
+            .find(|(asset_id, _)| *asset_id == base_asset_id)
+            .map(|(_, amount)| *amount)
+        else {
+            return Ok(amounts);
+        };
+
+        // Price the transfer with a nominal base amount, the fee hardly depends on it.
+        let priced = amounts
+            .iter()
+            .map(|(asset_id, amount)| match *asset_id == base_asset_id {
+                true => (*asset_id, 1),
+                false => (*asset_id, *amount),
+            })
+            .collect::<Vec<_>>();
+        let max_fee = self
+            .fund_predicate_tx(&priced)
+            .await?
+            .max_fee()
+            .unwrap_or_default();
+        let reserve = (max_fee as f64 * gas::SAFETY_MULTIPLIER).ceil() as u64;
+
+        Ok(amounts
+            .into_iter()
+            .map(|(asset_id, amount)| match asset_id == base_asset_id {
+                true => (asset_id, balance.saturating_sub(reserve)),
+                false => (asset_id, amount),
+            })
+            .filter(|(_, amount)| *amount > 0)
+            .collect())
+    }
+
+    fn asset_amount(&self, asset_id: Option<AssetId>, amount: u64) -> (AssetId, u64) {
+        let base_asset_id = *self.wallet.provider().unwrap().base_asset_id();
+        (asset_id.unwrap_or(base_asset_id), amount)
+    }
+
+    async fn fund_predicate_tx(
+        &self,
+        amounts: &[(AssetId, u64)],
+    ) -> std::result::Result<ScriptTransaction, TxError> {
+        let base_asset_id = *self.wallet.provider().unwrap().base_asset_id();
+        let predicate_address = self.predicate_address().await;
+
+        let mut totals: Vec<(AssetId, u64)> = Vec::new();
+        for (asset_id, amount) in amounts {
+            match totals.iter_mut().find(|(id, _)| id == asset_id) {
+                Some((_, total)) => *total += amount,
+                None => totals.push((*asset_id, *amount)),
+            }
+        }
+
+        let mut input_coins = Vec::new();
+        let mut output_coins = Vec::new();
+        for (asset_id, amount) in &totals {
+            input_coins.extend(
+                self.wallet
+                    .get_asset_inputs_for_amount(*asset_id, *amount, None)
+                    .await
+                    .map_err(TxError::not_submitted)?,
+            );
+            output_coins.extend(self.wallet.get_asset_outputs_for_amount(
+                &predicate_address,
+                *asset_id,
+                *amount,
+            ));
+        }
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            input_coins,
+            output_coins,
+            self.gas.tx_policies(),
+        );
+
+        tb.add_signer(self.wallet.clone())
+            .map_err(TxError::not_submitted)?;
+
+        let used_base_amount = totals
+            .iter()
+            .find(|(asset_id, _)| *asset_id == base_asset_id)
+            .map(|(_, amount)| *amount)
+            .unwrap_or(0);
+        self.wallet
+            .adjust_for_fee(&mut tb, used_base_amount)
+            .await
+            .map_err(TxError::not_submitted)?;
+
+        tb.build(&self.wallet.provider().unwrap())
+            .await
+            .map_err(TxError::not_submitted)
     }
 
     pub async fn send_to(
         &self,
-        address: &Bech32Address,
+        recipient: &Identity,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let tx = self.send_to_tx(recipient, asset_id, amount).await?;
+        self.submit_and_await(tx).await
+    }
+
+    pub async fn send_to_no_wait(
+        &self,
+        recipient: &Identity,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<TxId, TxError> {
+        let tx = self.send_to_tx(recipient, asset_id, amount).await?;
+        self.submit_no_wait(tx).await
+    }
+
+    pub async fn estimate_send_to(
+        &self,
+        recipient: &Identity,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        let tx = self.send_to_tx(recipient, asset_id, amount).await?;
+        self.dry_run(tx, None).await
+    }
+
+    async fn send_to_tx(
+        &self,
+        recipient: &Identity,
         asset_id: Option<AssetId>,
         amount: u64,
-    ) -> TxId {
+    ) -> std::result::Result<ScriptTransaction, TxError> {
         let asset_id =
             asset_id.unwrap_or_else(|| self.wallet.provider().unwrap().base_asset_id().clone());
 
-        //let gas = 100; // What is sensible?
-
         let predicate = self.predicate(self.wallet.address().into()).await;
+
+        match recipient {
+            Identity::Address(address) => {
+                self.spend_predicate_tx(&predicate, &(*address).into(), asset_id, amount)
+                    .await
+            }
+            Identity::ContractId(contract_id) => {
+                self.spend_predicate_to_contract_tx(&predicate, *contract_id, asset_id, amount)
+                    .await
+            }
+        }
+    }
+
+    async fn spend_predicate_tx(
+        &self,
+        predicate: &Predicate,
+        address: &Bech32Address,
+        asset_id: AssetId,
+        amount: u64,
+    ) -> std::result::Result<ScriptTransaction, TxError> {
         let input_coins = predicate
             .get_asset_inputs_for_amount(asset_id, amount, None)
             .await
-            .expect("should be able to get inputs");
+            .map_err(TxError::not_submitted)?;
         let output_coin = predicate.get_asset_outputs_for_amount(address, asset_id, amount);
 
         let mut tb = ScriptTransactionBuilder::prepare_transfer(
             input_coins,
             output_coin,
-            TxPolicies::default(),
+            self.gas.tx_policies(),
         );
 
         tb.add_signer(self.wallet.clone())
-            .expect("should be able to add signer");
+            .map_err(TxError::not_submitted)?;
 
         self.wallet
             .adjust_for_fee(&mut tb, 0)
             .await
-            .expect("should be able to adjust for fee");
+            .map_err(TxError::not_submitted)?;
 
-        let tx = tb
-            .build(&self.wallet.provider().unwrap())
+        tb.build(&self.wallet.provider().unwrap())
             .await
-            .expect("should be able to build tx");
+            .map_err(TxError::not_submitted)
+    }
+
+    /// Like [`Kpop::spend_predicate_tx`], but the coins are transferred to a contract
+    /// by a script.
+    async fn spend_predicate_to_contract_tx(
+        &self,
+        predicate: &Predicate,
+        contract_id: ContractId,
+        asset_id: AssetId,
+        amount: u64,
+    ) -> std::result::Result<ScriptTransaction, TxError> {
+        let mut inputs = vec![Input::contract(
+            UtxoId::new(Bytes32::zeroed(), 0),
+            Bytes32::zeroed(),
+            Bytes32::zeroed(),
+            TxPointer::default(),
+            contract_id,
+        )];
+        inputs.extend(
+            predicate
+                .get_asset_inputs_for_amount(asset_id, amount, None)
+                .await
+                .map_err(TxError::not_submitted)?,
+        );
+        let outputs = vec![
+            Output::contract(0, Bytes32::zeroed(), Bytes32::zeroed()),
+            Output::change(predicate.address().into(), 0, asset_id),
+        ];
 
-        let txid = tx.id(self.wallet.provider().unwrap().chain_id());
+        let mut tb = ScriptTransactionBuilder::prepare_contract_transfer(
+            contract_id,
+            amount,
+            asset_id,
+            inputs,
+            outputs,
+            self.gas.tx_policies(),
+        );
 
+        tb.add_signer(self.wallet.clone())
+            .map_err(TxError::not_submitted)?;
+
+        // The script moves the coins, so unlike with coin outputs the amount isn't
+        // accounted for unless it's passed in.
+        let base_asset_id = *self.wallet.provider().unwrap().base_asset_id();
+        let used_base_amount = if asset_id == base_asset_id { amount } else { 0 };
         self.wallet
+            .adjust_for_fee(&mut tb, used_base_amount)
+            .await
+            .map_err(TxError::not_submitted)?;
+
+        tb.build(&self.wallet.provider().unwrap())
+            .await
+            .map_err(TxError::not_submitted)
+    }
+
+    /// Spend from `owner`'s predicate without signing, paying fees from the predicate.
+    ///
+    /// The result has to be signed by the owner before it can be submitted with
+    /// [`Kpop::submit_signed`].
+    pub async fn build_unsigned_send_to(
+        &self,
+        owner: Address,
+        address: &Bech32Address,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<UnsignedTransaction, TxError> {
+        let provider = self.wallet.provider().unwrap();
+        let base_asset_id = provider.base_asset_id().clone();
+        let asset_id = asset_id.unwrap_or(base_asset_id);
+
+        let predicate = self.predicate(owner).await;
+        let input_coins = predicate
+            .get_asset_inputs_for_amount(asset_id, amount, None)
+            .await
+            .map_err(TxError::not_submitted)?;
+        let output_coin = predicate.get_asset_outputs_for_amount(address, asset_id, amount);
+
+        let mut tb = ScriptTransactionBuilder::prepare_transfer(
+            input_coins,
+            output_coin,
+            self.gas.tx_policies(),
+        )
+        .with_build_strategy(ScriptBuildStrategy::NoSignatures);
+
+        tb.witnesses_mut()
+            .push(UnsignedTransaction::placeholder_witness());
+
+        let used_base_amount = if asset_id == base_asset_id { amount } else { 0 };
+        predicate
+            .adjust_for_fee(&mut tb, used_base_amount)
+            .await
+            .map_err(TxError::not_submitted)?;
+
+        let tx = tb.build(provider).await.map_err(TxError::not_submitted)?;
+
+        Ok(UnsignedTransaction::new(
+            provider.chain_id(),
+            owner,
+            tx.into(),
+        ))
+    }
+
+    pub async fn submit_signed(
+        &self,
+        unsigned: UnsignedTransaction,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let tx = self.signed_tx(unsigned).await?;
+        self.submit_and_await(tx).await
+    }
+
+    pub async fn submit_signed_no_wait(
+        &self,
+        unsigned: UnsignedTransaction,
+    ) -> std::result::Result<TxId, TxError> {
+        let tx = self.signed_tx(unsigned).await?;
+        self.submit_no_wait(tx).await
+    }
+
+    async fn signed_tx(
+        &self,
+        unsigned: UnsignedTransaction,
+    ) -> std::result::Result<ScriptTransaction, TxError> {
+        if !unsigned.is_signed() {
+            return Err(TxError::not_submitted("transaction has not been signed"));
+        }
+
+        let mut tx = ScriptTransaction::from(unsigned.transaction);
+        tx.estimate_predicates(self.wallet.provider().unwrap(), None)
+            .await
+            .map_err(TxError::not_submitted)?;
+
+        Ok(tx)
+    }
+
+    /// Submit `tx` and wait for it like [`Kpop::await_tx`] does.
+    async fn submit_and_await(
+        &self,
+        tx: ScriptTransaction,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let txid = self.submit_no_wait(tx).await?;
+
+        self.await_tx(txid, COMMIT_TIMEOUT).await
+    }
+
+    async fn submit_no_wait(&self, tx: ScriptTransaction) -> std::result::Result<TxId, TxError> {
+        let provider = self.wallet.provider().unwrap();
+        let txid = tx.id(provider.chain_id());
+
+        provider
+            .send_transaction(tx)
+            .await
+            .map_err(|err| TxError::Rejected {
+                tx_id: txid,
+                reason: err.to_string(),
+            })
+    }
+
+    /// Wait for a transaction submitted without waiting to be included.
+    pub async fn await_tx(
+        &self,
+        txid: TxId,
+        timeout: Duration,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let provider = self.wallet.provider().unwrap();
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            // The node may not know about the transaction yet, so errors just mean
+            // trying again.
+            match provider.tx_status(&txid).await {
+                Ok(TxStatus::Success { .. }) => {
+                    if let Ok(Some(outcome)) = self.try_tx_status(txid).await {
+                        return Ok(outcome);
+                    }
+                }
+                Ok(TxStatus::Revert { reason, .. }) => {
+                    let reason = self
+                        .try_tx_status(txid)
+                        .await
+                        .ok()
+                        .flatten()
+                        .and_then(|outcome| outcome.revert_reason)
+                        .unwrap_or(reason);
+                    return Err(TxError::Reverted {
+                        tx_id: txid,
+                        reason,
+                    });
+                }
+                Ok(TxStatus::SqueezedOut { reason }) => {
+                    return Err(TxError::SqueezedOut {
+                        tx_id: txid,
+                        reason,
+                    });
+                }
+                Ok(TxStatus::Submitted) | Err(_) => {}
+            }
+
+            if tokio::time::Instant::now() >= deadline {
+                return Err(TxError::Timeout { tx_id: txid });
+            }
+            tokio::time::sleep(AWAIT_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn dry_run(
+        &self,
+        tx: ScriptTransaction,
+        log_decoder: Option<&LogDecoder>,
+    ) -> std::result::Result<Estimate, TxError> {
+        Estimate::dry_run(self.wallet.provider().unwrap(), tx, log_decoder).await
+    }
+
+    /// Policies for `tx` with a script gas limit estimated from a dry run, unless
+    /// the user has set a limit.
+    async fn estimated_tx_policies(
+        &self,
+        tx: ScriptTransaction,
+    ) -> std::result::Result<TxPolicies, TxError> {
+        if self.gas.gas_limit.is_some() {
+            return Ok(self.gas.tx_policies());
+        }
+
+        let receipts = self
+            .wallet
             .provider()
             .unwrap()
-            .send_transaction_and_await_commit(tx)
+            .dry_run_no_validation(tx)
+            .await
+            .map_err(TxError::not_submitted)?
+            .take_receipts();
+        let script_gas = gas::script_gas_used(&receipts)
+            .ok_or_else(|| TxError::not_submitted("dry run produced no script result"))?;
+
+        Ok(self.gas.tx_policies_for(script_gas))
+    }
+
+    /// Look up any past transaction, decoding claims contract logs.
+    pub async fn tx_status(&self, txid: TxId) -> Option<OperationOutcome> {
+        self.try_tx_status(txid)
+            .await
+            .expect("should be able to get transaction")
+    }
+
+    /// Like [`Kpop::tx_status`], but node errors are returned rather than panicking.
+    pub(crate) async fn try_tx_status(&self, txid: TxId) -> Result<Option<OperationOutcome>> {
+        let log_decoder = self.contract_instance().await.log_decoder();
+
+        OperationOutcome::fetch(self.wallet.provider().unwrap(), txid, Some(&log_decoder)).await
+    }
+
+    pub async fn disprove_claim(
+        &self,
+        claim_id: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        self.disprove_claim_in(self.contract_id, claim_id).await
+    }
+
+    pub async fn disprove_claim_in(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let call = self.disprove_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        let outcome = self.submit_and_await(tx).await?;
+        Ok(outcome.with_claim_ids(vec![claim_id]))
+    }
+
+    pub async fn disprove_claim_in_no_wait(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<TxId, TxError> {
+        let call = self.disprove_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.submit_no_wait(tx).await
+    }
+
+    pub async fn estimate_disprove_claim(
+        &self,
+        claim_id: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        self.estimate_disprove_claim_in(self.contract_id, claim_id)
             .await
-            .expect("should be able to send transaction");
+    }
 
-        txid
+    pub async fn estimate_disprove_claim_in(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        let call = self.disprove_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.dry_run(tx, Some(&call.log_decoder)).await
     }
 
-    pub async fn disprove_claim(&self, claim_id: u64) {
-        self.contract_instance()
+    async fn disprove_call(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<CallHandler<WalletUnlocked, ContractCall, ()>, TxError> {
+        let call = self
+            .contract_instance_at(contract_id)
             .await
             .methods()
             .disprove(claim_id)
-            .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000))
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
+            .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum);
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        Ok(call.with_tx_policies(self.estimated_tx_policies(tx).await?))
+    }
+
+    /// Disprove several claims in the active contract in one transaction.
+    pub async fn disprove_many(
+        &self,
+        claim_ids: &[u64],
+    ) -> std::result::Result<OperationOutcome, DisproveManyError> {
+        let claims = claim_ids
+            .iter()
+            .map(|claim_id| (self.contract_id, *claim_id))
+            .collect::<Vec<_>>();
+
+        self.disprove_many_in(&claims).await
+    }
+
+    /// Disprove several claims, possibly in different contracts, in one transaction.
+    ///
+    /// A multi-call is all or nothing, so every claim is dry run on its own first
+    /// and nothing is submitted if any of them would fail.
+    pub async fn disprove_many_in(
+        &self,
+        claims: &[(ContractId, u64)],
+    ) -> std::result::Result<OperationOutcome, DisproveManyError> {
+        let mut rejected = Vec::new();
+
+        for (contract_id, claim_id) in claims {
+            let estimate = self
+                .estimate_disprove_claim_in(*contract_id, *claim_id)
+                .await?;
+            if let Some(reason) = estimate.revert_reason {
+                rejected.push((*contract_id, *claim_id, reason));
+            }
+        }
+
+        if !rejected.is_empty() {
+            return Err(RejectedClaims(rejected).into());
+        }
+
+        let call = self.disprove_many_call(claims).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+        let outcome = self.submit_and_await(tx).await?;
+
+        Ok(outcome.with_claim_ids(claims.iter().map(|(_, claim_id)| *claim_id).collect()))
+    }
+
+    pub async fn estimate_disprove_many_in(
+        &self,
+        claims: &[(ContractId, u64)],
+    ) -> std::result::Result<Estimate, TxError> {
+        let call = self.disprove_many_call(claims).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.dry_run(tx, Some(&call.log_decoder)).await
+    }
+
+    async fn disprove_many_call(
+        &self,
+        claims: &[(ContractId, u64)],
+    ) -> std::result::Result<CallHandler<WalletUnlocked, Vec<ContractCall>, ()>, TxError> {
+        let mut call = CallHandler::new_multi_call(self.wallet.clone());
+
+        for (contract_id, claim_id) in claims {
+            call = call.add_call(
+                self.contract_instance_at(*contract_id)
+                    .await
+                    .methods()
+                    .disprove(*claim_id),
+            );
+        }
+
+        let call = call.with_variable_output_policy(VariableOutputPolicy::EstimateMinimum);
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        Ok(call.with_tx_policies(self.estimated_tx_policies(tx).await?))
+    }
+
+    pub async fn fulfill_claim(
+        &self,
+        claim_id: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        self.fulfill_claim_in(self.contract_id, claim_id).await
+    }
+
+    pub async fn fulfill_claim_in(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let call = self.fulfill_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        let outcome = self.submit_and_await(tx).await?;
+        Ok(outcome.with_claim_ids(vec![claim_id]))
+    }
+
+    pub async fn fulfill_claim_in_no_wait(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<TxId, TxError> {
+        let call = self.fulfill_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.submit_no_wait(tx).await
+    }
+
+    pub async fn estimate_fulfill_claim(
+        &self,
+        claim_id: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        self.estimate_fulfill_claim_in(self.contract_id, claim_id)
             .await
-            .expect("shoule be able to disprove");
     }
 
-    pub async fn fulfill_claim(&self, claim_id: u64) {
-        self.contract_instance()
+    pub async fn estimate_fulfill_claim_in(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        let call = self.fulfill_call(contract_id, claim_id).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.dry_run(tx, Some(&call.log_decoder)).await
+    }
+
+    async fn fulfill_call(
+        &self,
+        contract_id: ContractId,
+        claim_id: u64,
+    ) -> std::result::Result<CallHandler<WalletUnlocked, ContractCall, ()>, TxError> {
+        let call = self
+            .contract_instance_at(contract_id)
             .await
             .methods()
             .fulfill(claim_id)
-            .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000))
-            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
-            .call()
-            .await
-            .expect("shoule be able to fulfill");
+            .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum);
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        Ok(call.with_tx_policies(self.estimated_tx_policies(tx).await?))
+    }
+
+    pub async fn claim(
+        &self,
+        owner: Address,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<OperationOutcome, TxError> {
+        let call = self.claim_call(owner, asset_id, amount).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        let outcome = self.submit_and_await(tx).await?;
+        let claim_id = call
+            .get_response(outcome.receipts.clone())
+            .expect("make-claim script should return the claim ID")
+            .value;
+
+        Ok(outcome.with_claim_ids(vec![claim_id]))
+    }
+
+    pub async fn estimate_claim(
+        &self,
+        owner: Address,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<Estimate, TxError> {
+        let call = self.claim_call(owner, asset_id, amount).await?;
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
+
+        self.dry_run(tx, Some(&call.log_decoder)).await
     }
 
-    pub async fn claim(&self, owner: Address, asset_id: Option<AssetId>, amount: u64) -> u64 {
+    async fn claim_call(
+        &self,
+        owner: Address,
+        asset_id: Option<AssetId>,
+        amount: u64,
+    ) -> std::result::Result<CallHandler<WalletUnlocked, ScriptCall, u64>, TxError> {
         let asset_id =
             asset_id.unwrap_or_else(|| self.wallet.provider().unwrap().base_asset_id().clone());
         let predicate = self.predicate(owner).await;
         let input_coins = predicate
             .get_asset_inputs_for_amount(asset_id, amount, None)
             .await
-            .expect("failed to get inputs");
+            .map_err(TxError::not_submitted)?;
 
         let output_coins = predicate.get_asset_outputs_for_amount(predicate.address(), asset_id, 0);
 
-        let claim_id = self
+        let call = self
             .script_instance(owner)
             .await
-            .main(self.wallet.address(), 10_000_000, amount, asset_id.into())
+            .main(
+                self.wallet.address(),
+                CLAIM_FORWARDED_GAS,
+                amount,
+                asset_id.into(),
+            )
             .with_inputs(input_coins)
             .with_outputs(output_coins)
-            .with_contracts(&[&self.contract_instance().await])
-            .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000))
-            .call()
-            .await
-            .unwrap()
-            .value;
+            .with_contracts(&[&self.contract_instance().await]);
+        let tx = call.build_tx().await.map_err(TxError::not_submitted)?;
 
-        claim_id
+        Ok(call.with_tx_policies(self.estimated_tx_policies(tx).await?))
     }
 
     async fn predicate(&self, owner: Address) -> Predicate {
-        let predicate_binary_path = "../claimable/out/debug/claimable.bin";
+        self.versioned_predicate(&self.artifacts.current(), owner)
+            .await
+    }
 
-        let configurables = claimable_predicate::ClaimableConfigurables::default()
-            .with_MAKE_CLAIM_SCRIPT_HASH(
-                get_script_bytecode_hash(&self.script_instance(owner).await).await,
-            )
-            .unwrap()
-            .with_OWNER(owner)
-            .unwrap();
+    async fn versioned_predicate(&self, version: &ArtifactVersion, owner: Address) -> Predicate {
+        let predicate = version
+            .predicate()
+            .expect("should be able to read the predicate");
+        let code = kpop_core::address::predicate_bytecode(
+            predicate.program(),
+            self.script_hash(version, owner),
+            owner,
+        )
+        .expect("version's ABI should list the predicate configurables");
 
-        Predicate::load_from(predicate_binary_path)
-            .unwrap()
-            .with_configurables(configurables)
-            .with_provider(self.wallet.provider().unwrap().clone())
+        Predicate::from_code(code).with_provider(self.wallet.provider().unwrap().clone())
     }
 
     async fn script_instance(
         &self,
         owner: Address,
     ) -> make_claim_script::MakeClaim<WalletUnlocked> {
-        let script_binary_path = "../make-claim/out/debug/make-claim.bin";
-
-        let configurables = make_claim_script::MakeClaimConfigurables::default()
-            .with_CLAIMS_CONTRACT_ADDRESS(Bits256(*self.contract_id))
-            .unwrap()
-            .with_OWNER(owner)
-            .unwrap();
-
-        make_claim_script::MakeClaim::new(self.wallet.clone(), &script_binary_path)
-            .with_configurables(configurables)
+        self.versioned_script_instance(&self.artifacts.current(), owner)
+            .await
     }
 
-    async fn contract_instance(&self) -> claims_contract::ClaimsContract<WalletUnlocked> {
-        claims_contract::ClaimsContract::new(self.contract_id, self.wallet.clone())
-            .with_account(self.wallet.clone())
+    async fn versioned_script_instance(
+        &self,
+        version: &ArtifactVersion,
+        owner: Address,
+    ) -> make_claim_script::MakeClaim<WalletUnlocked> {
+        make_claim_script::MakeClaim::new(
+            self.wallet.clone(),
+            &version.script_path.to_string_lossy(),
+        )
+        .with_configurables(self.script_configurables(version, owner))
     }
-}
 
-async fn get_script_bytecode_hash(
-    script_instance: &make_claim_script::MakeClaim<WalletUnlocked>,
-) -> Bits256 {
-    use sha2::Digest;
-    use sha2::Sha256;
+    /// Hash of the script bytecode as it ends up in a transaction, i.e. with the
+    /// configurables applied.
+    fn script_hash(&self, version: &ArtifactVersion, owner: Address) -> Bytes32 {
+        let script = version.script().expect("should be able to read the script");
+        kpop_core::address::script_hash(script.program(), self.contract_id, owner)
+            .expect("version's ABI should list the script configurables")
+    }
 
-    let tx = script_instance
-        .main(Address::zeroed(), 0, 0, Bits256::zeroed())
-        .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000))
-        .build_tx()
-        .await
-        .unwrap();
+    /// The script configurables at the offsets of `version`'s build.
+    fn script_configurables(&self, version: &ArtifactVersion, owner: Address) -> Configurables {
+        let offsets = kpop_core::address::script_configurables(
+            &version
+                .script()
+                .expect("should be able to read the script")
+                .abi,
+            self.contract_id,
+            owner,
+        )
+        .expect("version's ABI should list the script configurables");
 
-    let mut hasher = Sha256::new();
-    hasher.update(tx.script());
-    let result = hasher.finalize();
-    let hash = Bits256(result.try_into().unwrap());
-    //let h = hex::encode(hash.0);
-    //println!("Bits: {h}");
+        Configurables::new(
+            offsets
+                .into_iter()
+                .map(|(offset, value)| (offset as u64, value.to_vec()))
+                .collect(),
+        )
+    }
 
-    hash
-}
+    async fn contract_instance(&self) -> claims_contract::ClaimsContract<WalletUnlocked> {
+        self.contract_instance_at(self.contract_id).await
+    }
 
-mod claimable_predicate {
-    use fuels::prelude::*;
-    abigen!(Predicate(
-        name = "Claimable",
-        abi = "../claimable/out/debug/claimable-abi.json"
-    ));
+    async fn contract_instance_at(
+        &self,
+        contract_id: ContractId,
+    ) -> claims_contract::ClaimsContract<WalletUnlocked> {
+        claims_contract::ClaimsContract::new(contract_id, self.wallet.clone())
+            .with_account(self.wallet.clone())
+    }
 }
 
 mod claims_contract {
@@ -279,6 +1257,3 @@ mod make_claim_script {
         abi = "../make-claim/out/debug/make-claim-abi.json",
     ));
 }
-
-#[cfg(test)]
-mod tests {}
@@ -1,19 +1,122 @@
 use std::str::FromStr;
 
+use clap::CommandFactory;
 use clap::Parser;
 use clap::Subcommand;
-use fuels::{crypto::SecretKey, prelude::*};
+use fuels::{crypto::SecretKey, prelude::*, types::Identity};
 
 use kpop;
 
 #[tokio::main]
 async fn main() {
-    let args = Args::parse();
-    let provider = Provider::connect(&args.provider_url)
-        .await
-        .expect("failed to connect");
+    let mut args = Args::parse();
+
+    if let Some(path) = &args.profile {
+        let profile = kpop::profile::Profile::load(path);
+        args.provider_url = args.provider_url.or(profile.provider_url);
+        args.private_key = args.private_key.or(profile.private_key);
+        args.contract_id = args.contract_id.or(profile.contract_id);
+    }
+
+    match &args.action {
+        Action::Completions { shell } => {
+            clap_complete::generate(*shell, &mut Args::command(), "kpop", &mut std::io::stdout());
+            return;
+        }
+        Action::Man { out_dir } => {
+            std::fs::create_dir_all(out_dir).expect("should be able to create man page directory");
+            write_man_pages(Args::command(), out_dir);
+            println!("Man pages written to {}", out_dir.display());
+            return;
+        }
+        _ => {}
+    }
+
+    // Signing happens on the offline machine, so it needs neither a node nor a contract.
+    if let Action::Tx {
+        action: TxAction::Sign { file, out },
+    } = &args.action
+    {
+        let pk = args
+            .private_key
+            .as_deref()
+            .map(|pk| SecretKey::from_str(pk).expect("should be able to parse private key"))
+            .expect("--private-key is needed to sign");
+        sign_tx(file, out.as_deref().unwrap_or(file), &pk);
+        return;
+    }
+
+    let assets = kpop::assets::AssetRegistry::load(&args.assets);
+    let address_book = kpop::address::AddressBook::load(&args.address_book);
+
+    if let Action::Assets { action } = args.action {
+        return assets_cmd(assets, action);
+    }
+
+    if let Action::Contacts { action } = args.action {
+        return contacts_cmd(address_book, action);
+    }
+
+    #[cfg(feature = "devnet")]
+    if let Action::Devnet(devnet_args) = args.action {
+        return devnet(devnet_args, args.provider_url, &args.registry).await;
+    }
+
+    let provider = Provider::connect(
+        args.provider_url
+            .as_deref()
+            .expect("--provider-url is needed"),
+    )
+    .await
+    .expect("failed to connect");
+
+    // Producing a number of blocks only needs the node.
+    if let Action::Node {
+        action: NodeAction::ProduceBlocks {
+            count: Some(count), ..
+        },
+    } = args.action
+    {
+        let height = kpop::node::advance(&provider, count).await;
+        println!("Produced {count} blocks, now at block {height}");
+        return;
+    }
+
+    // Building and submitting offline-signed transactions doesn't need the owner's
+    // key, any wallet will do.
+    let pk = match &args.private_key {
+        Some(pk) => SecretKey::from_str(pk).expect("should be able to parse private key"),
+        None if matches!(args.action, Action::Tx { .. }) => {
+            SecretKey::random(&mut rand::thread_rng())
+        }
+        None => panic!("--private-key is needed"),
+    };
+
+    let mut registry = kpop::registry::Registry::load(&args.registry);
+
+    if args.dry_run && args.contract_id.is_none() {
+        panic!("--dry-run needs a --contract-id, otherwise a new contract would be deployed");
+    }
+
+    if args.dry_run && args.no_wait {
+        panic!("--dry-run and --no-wait can't be combined");
+    }
 
-    let pk = SecretKey::from_str(&args.private_key).expect("should be able to parse private key");
+    if matches!(args.action, Action::Tx { .. }) && args.contract_id.is_none() {
+        panic!("tx needs a --contract-id, the predicate address depends on it");
+    }
+
+    if matches!(args.action, Action::Node { .. }) && args.contract_id.is_none() {
+        panic!("--until-claim-fulfillable needs a --contract-id to look the claim up");
+    }
+
+    let artifacts = match kpop::artifacts::Artifacts::load(&args.artifacts_dir) {
+        Ok(artifacts) => artifacts,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    };
 
     let kp = match args.contract_id {
         Some(contract_id) => {
@@ -21,143 +124,1371 @@ async fn main() {
                 ContractId::from_str(&contract_id).expect("should be able to parse contract ID");
             kpop::Kpop::load(provider, pk, contract_id)
         }
-        None => kpop::Kpop::deploy(provider, pk).await,
-    };
+        None => {
+            let kp = kpop::Kpop::deploy(provider, pk).await;
+            registry.add(kp.contract_id);
+            registry.save();
+            kp
+        }
+    }
+    .with_artifacts(artifacts)
+    .with_registry(registry)
+    .with_assets(assets)
+    .with_address_book(address_book)
+    .with_gas_options(kpop::gas::GasOptions {
+        gas_limit: args.gas_limit,
+        tip: args.tip,
+        max_fee: args.max_fee,
+    });
 
     match args.action {
-        Action::Info => println!("Kpop: {:?}", kp),
+        Action::Info => info(&kp).await,
         Action::Predicate => predicate_info(&kp).await,
         Action::Wallet => wallet_info(&kp).await,
         Action::Claims => claims(&kp).await,
-        Action::Fund { asset_id, amount } => fund(&kp, asset_id, amount).await,
+        Action::Fund {
+            asset,
+            amount,
+            all_except_gas,
+        } => {
+            let amounts = if all_except_gas {
+                exit_on_error(kp.all_except_gas().await)
+            } else if let Some(amount) = amount {
+                if asset.len() > 1 {
+                    panic!("give several assets as --asset <asset>:<amount> without --amount");
+                }
+                let asset_id = resolve_asset(&kp, asset.first().map(String::as_str));
+                vec![(asset_id, parse_amount(&kp, &asset_id, &amount))]
+            } else {
+                asset.iter().map(|s| parse_asset_amount(&kp, s)).collect()
+            };
+
+            fund(&kp, amounts, args.dry_run, args.no_wait).await
+        }
         Action::SendTo {
             recipient,
-            asset_id,
+            asset,
             amount,
-        } => send_to(&kp, recipient, asset_id, amount).await,
+        } => send_to(&kp, recipient, asset, amount, args.dry_run, args.no_wait).await,
         Action::Claim {
             owner,
-            asset_id,
+            asset,
             amount,
-        } => claim(&kp, owner, asset_id, amount).await,
-        Action::Disprove { claim_id } => disprove(&kp, claim_id).await,
-        Action::Fulfill { claim_id } => fulfill(&kp, claim_id).await,
+        } => {
+            if args.no_wait {
+                panic!("claim has to wait for the transaction to learn the claim ID");
+            }
+            claim(&kp, owner, asset, amount, args.dry_run).await
+        }
+        Action::Disprove {
+            claim_id,
+            in_contract,
+        } => disprove(&kp, claim_id, in_contract, args.dry_run, args.no_wait).await,
+        Action::Fulfill {
+            claim_id,
+            owner,
+            in_contract,
+        } => {
+            fulfill(
+                &kp,
+                claim_id,
+                owner,
+                in_contract,
+                args.dry_run,
+                args.no_wait,
+            )
+            .await
+        }
+        Action::Versions => versions(&kp).await,
+        Action::Migrate => migrate(&kp, args.dry_run, args.no_wait).await,
+        Action::Pin { name } => pin(&kp, &name).await,
+        Action::Contracts { action } => contracts(kp, action),
+        Action::Assets { .. } | Action::Contacts { .. } => {
+            unreachable!("local files are managed before connecting")
+        }
+        Action::Completions { .. } | Action::Man { .. } => {
+            unreachable!("documentation is generated before connecting")
+        }
+        Action::Node { action } => node(&kp, action).await,
+        #[cfg(feature = "devnet")]
+        Action::Devnet(_) => unreachable!("the devnet is handled before connecting"),
+        Action::Serve { addr, token } => kpop::server::serve(kp, addr, token).await,
+        Action::Monitor(monitor_args) => monitor(&kp, monitor_args).await,
+        Action::Tui { owner } => tui(kp, owner).await,
+        Action::Tx { action } => tx(&kp, action, args.no_wait).await,
+        Action::Batch(batch_args) => batch(&kp, batch_args).await,
     };
 }
 
+/// Only what identifies the setup, never the wallet's key.
+async fn info(kp: &kpop::Kpop) {
+    println!("Node: {}", kp.wallet.provider().unwrap().url());
+    println!("Wallet address: {}", kp.wallet.address());
+    println!("Predicate address: {}", kp.predicate_address().await);
+    println!("Contract: {}", kp.contract_id);
+    for contract_id in kp.contract_ids() {
+        if contract_id != kp.contract_id {
+            println!("Registered contract: {contract_id}");
+        }
+    }
+    println!("Artifacts version: {}", kp.artifacts.current().name);
+
+    let gas = &kp.gas;
+    for (name, value) in [
+        ("Gas limit", gas.gas_limit),
+        ("Tip", gas.tip),
+        ("Max fee", gas.max_fee),
+    ] {
+        if let Some(value) = value {
+            println!("{name}: {value}");
+        }
+    }
+}
+
 async fn predicate_info(kp: &kpop::Kpop) {
     let address = kp.predicate_address().await;
     println!("Predicate address: {}", address);
-    let balance = kp.predicate_balance().await;
-    println!("Balance: {:?}", balance);
+    print_balance(kp, kp.predicate_balance().await);
 }
 
 async fn wallet_info(kp: &kpop::Kpop) {
     println!("Wallet address: {}", kp.wallet.address());
-    let balance = kp.wallet_balance().await;
-    println!("Balance: {:?}", balance);
+    print_balance(kp, kp.wallet_balance().await);
+}
+
+fn print_balance(kp: &kpop::Kpop, balance: std::collections::HashMap<String, u64>) {
+    let balance = kpop::assets::sorted_balance(balance);
+
+    if balance.is_empty() {
+        println!("Balance: nothing");
+    }
+
+    for (asset_id, amount) in balance {
+        println!("Balance: {}", kp.assets.display(&asset_id, amount));
+    }
 }
 
 async fn claims(kp: &kpop::Kpop) {
-    let claims = kp.get_claims().await;
-    println!("Claims: {:?}", claims);
+    for (contract_id, claim) in kp.get_all_claims().await {
+        println!(
+            "Claim {} in {contract_id}: {} from {} to {}, filed at block {}",
+            claim.id,
+            kp.assets.display(&claim.asset, claim.amount),
+            kp.address_book.display_address(&claim.owner),
+            kp.address_book.display_address(&claim.recipient),
+            claim.block_height
+        );
+    }
+}
+
+async fn fund(kp: &kpop::Kpop, amounts: Vec<(AssetId, u64)>, dry_run: bool, no_wait: bool) {
+    if amounts.is_empty() {
+        println!("Nothing to fund");
+        return;
+    }
+
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_fund_predicate_many(&amounts).await),
+        );
+        return;
+    }
+
+    if no_wait {
+        print_submitted(&exit_on_error(
+            kp.fund_predicate_many_no_wait(&amounts).await,
+        ));
+        return;
+    }
+
+    for (asset_id, amount) in &amounts {
+        println!("Funding {}", kp.assets.display(asset_id, *amount));
+    }
+    print_outcome(&exit_on_error(kp.fund_predicate_many(&amounts).await));
+}
+
+/// `<symbol or asset id>:<amount>`
+fn parse_asset_amount(kp: &kpop::Kpop, s: &str) -> (AssetId, u64) {
+    let (asset, amount) = exit_on_error(
+        s.split_once(':')
+            .ok_or_else(|| format!("asset should be given as <asset>:<amount>, not {s}")),
+    );
+    let asset_id = resolve_asset(kp, Some(asset));
+
+    (asset_id, parse_amount(kp, &asset_id, amount))
+}
+
+/// A registered symbol or a hex asset ID, the base asset if not given.
+fn resolve_asset(kp: &kpop::Kpop, asset: Option<&str>) -> AssetId {
+    match asset {
+        Some(asset) => exit_on_error(kp.assets.resolve(asset).ok_or_else(|| {
+            format!("unknown asset {asset}, register it with `kpop assets add` or give its ID")
+        })),
+        None => *kp.wallet.provider().unwrap().base_asset_id(),
+    }
 }
 
-async fn fund(kp: &kpop::Kpop, asset_id: Option<String>, amount: u64) {
-    let asset_id =
-        asset_id.map(|s| AssetId::from_str(&s).expect("asset_id should be a valid hex string"));
+/// An address book name, bech32 or hex address.
+fn parse_address(kp: &kpop::Kpop, s: &str) -> Address {
+    exit_on_error(kpop::address::parse_address(s, &kp.address_book))
+}
 
-    let txid = kp.fund_predicate(asset_id, amount).await;
+/// Like [`parse_address`], or a contract as `contract:<hex>`.
+fn parse_identity(kp: &kpop::Kpop, s: &str) -> Identity {
+    exit_on_error(kpop::address::parse_identity(s, &kp.address_book))
+}
 
-    println!("Transaction: {txid}");
+/// Whole units of `asset_id`, or base units if it isn't registered.
+fn parse_amount(kp: &kpop::Kpop, asset_id: &AssetId, amount: &str) -> u64 {
+    exit_on_error(kp.assets.parse_amount(asset_id, amount))
 }
 
-async fn send_to(kp: &kpop::Kpop, recipient: String, asset_id: Option<String>, amount: u64) {
-    let recipient = Bech32Address::from_str(&recipient)
-        .expect("recipient should be a bech32 formatted address");
+async fn send_to(
+    kp: &kpop::Kpop,
+    recipient: String,
+    asset: Option<String>,
+    amount: String,
+    dry_run: bool,
+    no_wait: bool,
+) {
+    let recipient = parse_identity(kp, &recipient);
+
+    let asset_id = resolve_asset(kp, asset.as_deref());
+    let amount = parse_amount(kp, &asset_id, &amount);
+    let asset_id = Some(asset_id);
 
-    let asset_id =
-        asset_id.map(|s| AssetId::from_str(&s).expect("asset_id should be a valid hex string"));
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_send_to(&recipient, asset_id, amount).await),
+        );
+        return;
+    }
 
-    let txid = kp.send_to(&recipient, asset_id, amount).await;
+    if no_wait {
+        print_submitted(&exit_on_error(
+            kp.send_to_no_wait(&recipient, asset_id, amount).await,
+        ));
+        return;
+    }
 
-    println!("Trasaction: {txid}");
+    print_outcome(&exit_on_error(
+        kp.send_to(&recipient, asset_id, amount).await,
+    ));
 }
 
-async fn claim(kp: &kpop::Kpop, owner: String, asset_id: Option<String>, amount: u64) {
-    let owner =
-        Bech32Address::from_str(&owner).expect("owner should be a bech32 formatted address");
+async fn claim(
+    kp: &kpop::Kpop,
+    owner: String,
+    asset: Option<String>,
+    amount: String,
+    dry_run: bool,
+) {
+    let owner = parse_address(kp, &owner);
 
-    let asset_id =
-        asset_id.map(|s| AssetId::from_str(&s).expect("asset_id should be a valid hex string"));
+    let asset_id = resolve_asset(kp, asset.as_deref());
+    let amount = parse_amount(kp, &asset_id, &amount);
+    let asset_id = Some(asset_id);
 
-    let claim_id = kp.claim(owner.into(), asset_id, amount).await;
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_claim(owner, asset_id, amount).await),
+        );
+        return;
+    }
+
+    let outcome = exit_on_error(kp.claim(owner, asset_id, amount).await);
+    let claim_id = outcome.claim_ids[0];
 
     println!("Made claim {claim_id}");
+    print_outcome(&outcome);
+}
+
+/// The contract passed with `--in-contract`, or else the only known contract
+/// holding claim `claim_id` against `owner`.
+async fn claim_contract(
+    kp: &kpop::Kpop,
+    owner: Address,
+    claim_id: u64,
+    in_contract: &Option<String>,
+) -> ContractId {
+    if let Some(contract_id) = in_contract {
+        return ContractId::from_str(contract_id).expect("should be able to parse contract ID");
+    }
+
+    match kp.locate_claim_of(owner, claim_id).await.as_slice() {
+        [contract_id] => *contract_id,
+        [] => panic!(
+            "no claim {claim_id} against {} in any known contract",
+            kp.address_book.display_address(&owner)
+        ),
+        _ => panic!("claim {claim_id} exists in several contracts, pass --in-contract"),
+    }
+}
+
+async fn disprove(
+    kp: &kpop::Kpop,
+    mut claim_ids: Vec<u64>,
+    in_contract: Option<String>,
+    dry_run: bool,
+    no_wait: bool,
+) {
+    claim_ids.sort_unstable();
+    claim_ids.dedup();
+
+    let owner = kp.wallet.address().into();
+    let mut claims = Vec::new();
+    for claim_id in claim_ids {
+        let contract_id = claim_contract(kp, owner, claim_id, &in_contract).await;
+        claims.push((contract_id, claim_id));
+    }
+
+    if let [(contract_id, claim_id)] = claims[..] {
+        return disprove_one(kp, contract_id, claim_id, dry_run, no_wait).await;
+    }
+
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_disprove_many_in(&claims).await),
+        );
+        return;
+    }
+
+    if no_wait {
+        panic!("--no-wait only works with a single --claim-id");
+    }
+
+    match kp.disprove_many_in(&claims).await {
+        Ok(outcome) => {
+            for (contract_id, claim_id) in &claims {
+                println!("Disproved claim {claim_id} in {contract_id}");
+            }
+            print_outcome(&outcome);
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn disprove_one(
+    kp: &kpop::Kpop,
+    contract_id: ContractId,
+    claim_id: u64,
+    dry_run: bool,
+    no_wait: bool,
+) {
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_disprove_claim_in(contract_id, claim_id).await),
+        );
+        return;
+    }
+
+    if no_wait {
+        print_submitted(&exit_on_error(
+            kp.disprove_claim_in_no_wait(contract_id, claim_id).await,
+        ));
+        return;
+    }
+
+    let outcome = exit_on_error(kp.disprove_claim_in(contract_id, claim_id).await);
+    println!("Disproved claim {claim_id} in {contract_id}");
+    print_outcome(&outcome);
+}
+
+async fn fulfill(
+    kp: &kpop::Kpop,
+    claim_id: u64,
+    owner: Option<String>,
+    in_contract: Option<String>,
+    dry_run: bool,
+    no_wait: bool,
+) {
+    // Only used to look the claim up, which --in-contract skips.
+    let owner = owner
+        .map(|owner| parse_address(kp, &owner))
+        .unwrap_or_else(|| kp.wallet.address().into());
+    let contract_id = claim_contract(kp, owner, claim_id, &in_contract).await;
+
+    if dry_run {
+        print_estimate(
+            kp,
+            &exit_on_error(kp.estimate_fulfill_claim_in(contract_id, claim_id).await),
+        );
+        return;
+    }
+
+    if no_wait {
+        print_submitted(&exit_on_error(
+            kp.fulfill_claim_in_no_wait(contract_id, claim_id).await,
+        ));
+        return;
+    }
+
+    let outcome = exit_on_error(kp.fulfill_claim_in(contract_id, claim_id).await);
+    println!("Fulfilled claim {claim_id} in {contract_id}");
+    print_outcome(&outcome);
+}
+
+async fn monitor(kp: &kpop::Kpop, args: MonitorArgs) {
+    use kpop::notify::{CommandNotifier, EmailNotifier, Notifier, WebhookNotifier};
+
+    let owner = args
+        .owner
+        .map(|s| parse_address(kp, &s))
+        .unwrap_or_else(|| kp.wallet.address().into());
+
+    let mut notifiers: Vec<Box<dyn Notifier>> = Vec::new();
+
+    for url in args.webhook {
+        notifiers.push(Box::new(WebhookNotifier::new(url)));
+    }
+
+    for command in args.command {
+        notifiers.push(Box::new(CommandNotifier { command }));
+    }
+
+    if let Some(to) = args.email_to {
+        notifiers.push(Box::new(EmailNotifier {
+            host: args.smtp_host.expect("--smtp-host is needed to send email"),
+            port: args.smtp_port,
+            from: args
+                .email_from
+                .expect("--email-from is needed to send email"),
+            to,
+            credentials: args.smtp_user.zip(args.smtp_password),
+            starttls: !args.no_starttls,
+        }));
+    }
+
+    println!(
+        "Monitoring claims against {}",
+        kp.address_book.display_address(&owner)
+    );
+    kpop::notify::monitor(
+        kp,
+        owner,
+        std::time::Duration::from_secs(args.poll_interval),
+        &notifiers,
+    )
+    .await;
+}
+
+async fn tui(kp: kpop::Kpop, owners: Vec<String>) {
+    let mut owners = owners
+        .iter()
+        .map(|s| parse_address(&kp, s))
+        .collect::<Vec<_>>();
+
+    // Claims can only be looked up by owner, everyone in the address book is a
+    // likely owner of claims the wallet filed.
+    for (_, identity) in kp.address_book.contacts() {
+        if let Identity::Address(address) = identity {
+            if !owners.contains(address) {
+                owners.push(*address);
+            }
+        }
+    }
+
+    kpop::tui::run(kp, owners)
+        .await
+        .expect("should be able to drive the terminal");
+}
+
+async fn tx(kp: &kpop::Kpop, action: TxAction, no_wait: bool) {
+    match action {
+        TxAction::Build {
+            owner,
+            recipient,
+            asset,
+            amount,
+            out,
+        } => {
+            let owner = parse_address(kp, &owner);
+            let recipient = Bech32Address::from(parse_address(kp, &recipient));
+            let asset_id = resolve_asset(kp, asset.as_deref());
+            let amount = parse_amount(kp, &asset_id, &amount);
+
+            let unsigned = exit_on_error(
+                kp.build_unsigned_send_to(owner, &recipient, Some(asset_id), amount)
+                    .await,
+            );
+            unsigned.save(&out);
+
+            println!("Transaction: {}", unsigned.tx_id);
+            println!(
+                "Sign it offline with `kpop tx sign --file {}`",
+                out.display()
+            );
+        }
+        TxAction::Sign { .. } => unreachable!("signing is handled before connecting"),
+        TxAction::Wait { tx_id, timeout } => {
+            let txid = fuels::tx::TxId::from_str(&tx_id).expect("should be able to parse tx ID");
+
+            match kp
+                .await_tx(txid, std::time::Duration::from_secs(timeout))
+                .await
+            {
+                Ok(outcome) => print_outcome(&outcome),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        TxAction::Status { tx_id } => {
+            let txid = fuels::tx::TxId::from_str(&tx_id).expect("should be able to parse tx ID");
+
+            match kp.tx_status(txid).await {
+                Some(outcome) => print_outcome(&outcome),
+                None => println!("Unknown transaction {txid}"),
+            }
+        }
+        TxAction::Submit { file } => {
+            let signed = exit_on_error(kpop::unsigned::UnsignedTransaction::load(&file));
+            if no_wait {
+                print_submitted(&exit_on_error(kp.submit_signed_no_wait(signed).await));
+                return;
+            }
+
+            print_outcome(&exit_on_error(kp.submit_signed(signed).await));
+        }
+    }
+}
+
+fn sign_tx(file: &std::path::Path, out: &std::path::Path, pk: &SecretKey) {
+    use fuels::tx::field::Outputs;
+
+    let mut unsigned = exit_on_error(kpop::unsigned::UnsignedTransaction::load(file));
+
+    println!(
+        "Signing {} for {}",
+        unsigned.tx_id,
+        kpop::address::display_address(&unsigned.owner)
+    );
+    for output in unsigned.transaction.outputs() {
+        if let (Some(to), Some(amount), Some(asset_id)) =
+            (output.to(), output.amount(), output.asset_id())
+        {
+            println!(
+                "Output: {amount} of {asset_id} to {}",
+                kpop::address::display_address(to)
+            );
+        }
+    }
+
+    exit_on_error(unsigned.sign(pk));
+    exit_on_error(unsigned.save(out));
+
+    println!("Signed transaction written to {}", out.display());
+}
+
+async fn batch(kp: &kpop::Kpop, args: BatchArgs) {
+    let rows = kpop::batch::load(&args.file);
+
+    let operations = match kpop::batch::validate(&rows, &kp.address_book) {
+        Ok(operations) => operations,
+        Err(errors) => {
+            for (row, err) in errors {
+                eprintln!("Row {row}: {err}");
+            }
+            eprintln!("Nothing was submitted");
+            std::process::exit(1);
+        }
+    };
+
+    let options = kpop::batch::BatchOptions {
+        concurrency: args.concurrency,
+        retries: args.retries,
+        retry_delay: std::time::Duration::from_secs(args.retry_delay),
+    };
+    let results = kpop::batch::execute(kp, operations, options).await;
+    kpop::batch::write_report(&args.report, &results);
+
+    let failed = results.iter().filter(|result| !result.is_ok()).count();
+    for result in results.iter().filter(|result| !result.is_ok()) {
+        eprintln!(
+            "Row {} ({}): {}",
+            result.row,
+            result.op,
+            result.error.as_deref().unwrap_or_default()
+        );
+    }
+    println!(
+        "{} succeeded, {failed} failed, report written to {}",
+        results.len() - failed,
+        args.report.display()
+    );
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn contracts(kp: kpop::Kpop, action: ContractsAction) {
+    let mut registry = kp.registry;
+
+    match action {
+        ContractsAction::List => {
+            for contract_id in registry.contracts() {
+                let marker = if *contract_id == kp.contract_id {
+                    "*"
+                } else {
+                    " "
+                };
+                println!("{marker} {contract_id}");
+            }
+        }
+        ContractsAction::Add { contract_id } => {
+            let contract_id =
+                ContractId::from_str(&contract_id).expect("should be able to parse contract ID");
+            if registry.add(contract_id) {
+                registry.save();
+            }
+            println!("Added {contract_id}");
+        }
+        ContractsAction::Remove { contract_id } => {
+            let contract_id =
+                ContractId::from_str(&contract_id).expect("should be able to parse contract ID");
+            if registry.remove(&contract_id) {
+                registry.save();
+            }
+            println!("Removed {contract_id}");
+        }
+    }
+}
+
+fn assets_cmd(mut assets: kpop::assets::AssetRegistry, action: AssetsAction) {
+    match action {
+        AssetsAction::List => {
+            for asset in assets.assets() {
+                println!(
+                    "{} {} ({} decimals)",
+                    asset.symbol, asset.asset_id, asset.decimals
+                );
+            }
+        }
+        AssetsAction::Add {
+            symbol,
+            asset_id,
+            decimals,
+        } => {
+            let asset_id =
+                AssetId::from_str(&asset_id).expect("asset_id should be a valid hex string");
+            assets.add(kpop::assets::AssetInfo {
+                symbol: symbol.clone(),
+                asset_id,
+                decimals,
+            });
+            assets.save();
+            println!("Added {symbol}");
+        }
+        AssetsAction::Remove { symbol } => {
+            if assets.remove(&symbol) {
+                assets.save();
+            }
+            println!("Removed {symbol}");
+        }
+    }
+}
+
+fn contacts_cmd(mut address_book: kpop::address::AddressBook, action: ContactsAction) {
+    match action {
+        ContactsAction::List => {
+            for (name, identity) in address_book.contacts() {
+                println!("{name} {}", kpop::address::display_identity(identity));
+            }
+        }
+        ContactsAction::Add { name, address } => {
+            let identity = exit_on_error(kpop::address::parse_identity(&address, &address_book));
+            exit_on_error(address_book.add(&name, identity));
+            address_book.save();
+            println!("Added {}", address_book.display_identity(&identity));
+        }
+        ContactsAction::Remove { name } => {
+            if address_book.remove(&name) {
+                address_book.save();
+            }
+            println!("Removed {name}");
+        }
+    }
+}
+
+async fn node(kp: &kpop::Kpop, action: NodeAction) {
+    let NodeAction::ProduceBlocks {
+        until_claim_fulfillable: Some(claim_id),
+        owner,
+        in_contract,
+        ..
+    } = action
+    else {
+        unreachable!("producing a number of blocks is handled before loading the wallet")
+    };
+
+    let owner = owner
+        .map(|s| parse_address(kp, &s))
+        .unwrap_or_else(|| kp.wallet.address().into());
+    let claims = match in_contract {
+        Some(contract_id) => {
+            let contract_id =
+                ContractId::from_str(&contract_id).expect("should be able to parse contract ID");
+            kp.get_claims_of(contract_id, owner)
+                .await
+                .into_iter()
+                .map(|claim| (contract_id, claim))
+                .collect()
+        }
+        None => kp.get_all_claims_of(owner).await,
+    };
+    let claim = match claims
+        .iter()
+        .filter(|(_, claim)| claim.id == claim_id)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        [(_, claim)] => claim.clone(),
+        [] => panic!(
+            "no claim {claim_id} against {} in any known contract",
+            kp.address_book.display_address(&owner)
+        ),
+        _ => panic!("claim {claim_id} exists in several contracts, pass --in-contract"),
+    };
+
+    let provider = kp.wallet.provider().unwrap();
+    let height = kpop::node::advance_until_fulfillable(provider, &claim).await;
+    println!("Claim {claim_id} can be fulfilled, now at block {height}");
+}
+
+#[cfg(feature = "devnet")]
+async fn devnet(args: DevnetArgs, provider_url: Option<String>, registry: &str) {
+    use kpop::devnet::{Devnet, DEFAULT_PROFILE_PATH, PREFUNDED_KEYS};
+
+    match args.action {
+        Some(DevnetAction::Advance { blocks }) => {
+            // Without a node given, advance the one `kpop devnet` last started here.
+            let provider_url = provider_url
+                .or_else(|| {
+                    std::path::Path::new(DEFAULT_PROFILE_PATH)
+                        .exists()
+                        .then(|| kpop::profile::Profile::load(DEFAULT_PROFILE_PATH))
+                        .and_then(|profile| profile.provider_url)
+                })
+                .expect("--provider-url is needed");
+            let provider = Provider::connect(provider_url)
+                .await
+                .expect("failed to connect");
+
+            let height = kpop::node::advance(&provider, blocks).await;
+            println!("Produced {blocks} blocks, now at block {height}");
+        }
+        None => {
+            let devnet = Devnet::launch(args.addr).await;
+
+            let mut registry = kpop::registry::Registry::load(registry);
+            if registry.add(devnet.kpop.contract_id) {
+                registry.save();
+            }
+            devnet.profile().save(&args.out);
+
+            println!("Node: {}", devnet.provider.url());
+            println!("Contract: {}", devnet.kpop.contract_id);
+            for (wallet, key) in devnet.wallets.iter().zip(PREFUNDED_KEYS) {
+                println!(
+                    "Wallet: {} with private key {key}",
+                    kpop::address::display_address(&wallet.address().into())
+                );
+            }
+            let out = args.out.display();
+            println!("Profile for the first wallet written to {out}");
+            println!("Use it with `kpop --profile {out} <command>`");
+            println!(
+                "Skip the challenge period with `kpop devnet advance --blocks {}`",
+                kpop::CHALLENGE_PERIOD
+            );
+            println!("Press Ctrl-C to stop");
+
+            tokio::signal::ctrl_c()
+                .await
+                .expect("should be able to wait for Ctrl-C");
+        }
+    }
+}
+
+async fn versions(kp: &kpop::Kpop) {
+    let current = kp.artifacts.current();
+
+    for (version, address) in kp.predicate_addresses().await {
+        let marker = if version == current { "*" } else { " " };
+        println!("{marker} {}: {address}", version.name);
+    }
+
+    for (version, balance) in kp.stranded_balances().await {
+        for (asset_id, amount) in kpop::assets::sorted_balance(balance) {
+            println!(
+                "Stranded at {}: {}",
+                version.name,
+                kp.assets.display(&asset_id, amount)
+            );
+        }
+    }
+}
+
+async fn migrate(kp: &kpop::Kpop, dry_run: bool, no_wait: bool) {
+    if dry_run {
+        for estimate in exit_on_error(kp.estimate_migrate().await) {
+            print_estimate(kp, &estimate);
+        }
+        return;
+    }
+
+    if no_wait {
+        for txid in exit_on_error(kp.migrate_no_wait().await) {
+            print_submitted(&txid);
+        }
+        return;
+    }
+
+    let outcomes = exit_on_error(kp.migrate().await);
+
+    if outcomes.is_empty() {
+        println!("Nothing to migrate");
+    }
+
+    for outcome in outcomes {
+        print_outcome(&outcome);
+    }
+}
+
+async fn pin(kp: &kpop::Kpop, name: &str) {
+    let mut artifacts = kp.artifacts.clone();
+    let version = exit_on_error(artifacts.pin(name));
+
+    let kp = kp.clone().with_artifacts(artifacts);
+    println!("Pinned {}: {}", version.name, kp.predicate_address().await);
+}
+
+/// `kpop.1` for `cmd`, and e.g. `kpop-tx-sign.1` for each of its subcommands.
+fn write_man_pages(mut cmd: clap::Command, out_dir: &std::path::Path) {
+    cmd.build();
+    write_man_page(&cmd, "kpop", out_dir);
+}
+
+fn write_man_page(cmd: &clap::Command, name: &str, out_dir: &std::path::Path) {
+    let mut page = Vec::new();
+    clap_mangen::Man::new(cmd.clone().name(name))
+        .render(&mut page)
+        .expect("should be able to render man page");
+    std::fs::write(out_dir.join(format!("{name}.1")), page)
+        .expect("should be able to write man page");
+
+    for sub in cmd.get_subcommands().filter(|sub| sub.get_name() != "help") {
+        write_man_page(sub, &format!("{name}-{}", sub.get_name()), out_dir);
+    }
+}
+
+fn print_submitted(txid: &fuels::tx::TxId) {
+    println!("Submitted: {txid}");
+    println!("Wait for it with `kpop tx wait {txid}`");
 }
 
-async fn disprove(kp: &kpop::Kpop, claim_id: u64) {
-    kp.disprove_claim(claim_id).await;
-    println!("Disproved claim {claim_id}")
+/// The value of `result`, or its error printed and the process ended.
+fn exit_on_error<T>(result: std::result::Result<T, impl std::fmt::Display>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("{err}");
+        std::process::exit(1);
+    })
 }
 
-async fn fulfill(kp: &kpop::Kpop, claim_id: u64) {
-    kp.fulfill_claim(claim_id).await;
-    println!("Fulfilld claim {claim_id}")
+fn print_outcome(outcome: &kpop::outcome::OperationOutcome) {
+    println!("Transaction: {}", outcome.tx_id);
+
+    match outcome.block_height {
+        Some(height) => println!("Block: {height}"),
+        None => println!("Not included in a block yet"),
+    }
+
+    println!("Gas used: {}", outcome.gas_used);
+    println!("Fee: {}", outcome.total_fee);
+
+    for log in &outcome.logs {
+        println!("Log: {log}");
+    }
+
+    if let Some(reason) = &outcome.revert_reason {
+        println!("Reverted: {reason}");
+    }
+}
+
+fn print_estimate(kp: &kpop::Kpop, estimate: &kpop::estimate::Estimate) {
+    println!("Dry run, nothing was submitted");
+    println!("Gas used: {}", estimate.gas_used);
+    println!("Fee: {}", estimate.total_fee);
+
+    for input in &estimate.inputs {
+        match input.contract_id() {
+            Some(contract_id) => println!(
+                "Input: {}",
+                kp.address_book
+                    .display_identity(&Identity::ContractId(*contract_id))
+            ),
+            None => println!(
+                "Input: {} from {}",
+                kp.assets.display(
+                    &input
+                        .asset_id(&AssetId::zeroed())
+                        .copied()
+                        .unwrap_or_default(),
+                    input.amount().unwrap_or_default()
+                ),
+                kp.address_book
+                    .display_address(input.input_owner().unwrap_or(&Address::zeroed()))
+            ),
+        }
+    }
+
+    for output in &estimate.outputs {
+        match output.to() {
+            Some(to) => println!(
+                "Output: {} to {}",
+                kp.assets.display(
+                    &output.asset_id().copied().unwrap_or_default(),
+                    output.amount().unwrap_or_default()
+                ),
+                kp.address_book.display_address(to)
+            ),
+            None => println!("Output: {:?}", output),
+        }
+    }
+
+    if let Some(reason) = &estimate.revert_reason {
+        println!("Reverted: {reason}");
+    }
 }
 
+/// Keep funds in a predicate that anyone can claim, unless the owner disproves the
+/// claim within the challenge period.
 #[derive(Parser)]
+#[command(name = "kpop", version)]
 struct Args {
-    /// URL of Fuel node to connect to
+    /// URL of Fuel node to connect to. Not needed for `tx sign`.
     #[arg(long, env)]
-    provider_url: String,
+    provider_url: Option<String>,
 
-    /// Private key - shh, don't tell anyone!!!
+    /// Private key - shh, don't tell anyone!!! Not needed for `tx build` and `tx submit`.
     #[arg(long, env)]
-    private_key: String,
+    private_key: Option<String>,
 
     /// ID of claims contract. Will deploy a new one if not provided.
     #[arg(long, env)]
     contract_id: Option<String>,
 
+    /// Directory with pinned predicate and script versions
+    #[arg(long, env, default_value = kpop::artifacts::DEFAULT_ARTIFACTS_DIR)]
+    artifacts_dir: String,
+
+    /// File listing every known claims contract
+    #[arg(long, env, default_value = kpop::registry::DEFAULT_REGISTRY_PATH)]
+    registry: String,
+
+    /// File mapping asset symbols to IDs and decimals
+    #[arg(long, env, default_value = kpop::assets::DEFAULT_ASSETS_PATH)]
+    assets: String,
+
+    /// File naming addresses and contracts, usable wherever one is expected
+    #[arg(long, env, default_value = kpop::address::DEFAULT_ADDRESS_BOOK_PATH)]
+    address_book: String,
+
+    /// File with any of the settings above, e.g. written by `kpop devnet`. Settings
+    /// given directly take precedence.
+    #[arg(long, env = "KPOP_PROFILE")]
+    profile: Option<std::path::PathBuf>,
+
+    /// Simulate state-changing commands instead of submitting them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Return after submitting instead of waiting for inclusion
+    #[arg(long, global = true)]
+    no_wait: bool,
+
+    /// Script gas limit. Estimated per call if not provided.
+    #[arg(long, env, global = true)]
+    gas_limit: Option<u64>,
+
+    /// Tip paid to the block producer
+    #[arg(long, env, global = true)]
+    tip: Option<u64>,
+
+    /// Maximum fee the transaction may pay
+    #[arg(long, env, global = true)]
+    max_fee: Option<u64>,
+
     #[command(subcommand)]
     action: Action,
 }
 
 #[derive(Subcommand)]
 enum Action {
+    /// Print the wallet, contract and settings in use
     Info,
+    /// Show the wallet's predicate address and balance
     Predicate,
+    /// Show the wallet address and balance
     Wallet,
+    /// List claims against the wallet in every known contract
     Claims,
+    /// Move funds from the wallet into its predicate
     Fund {
-        #[arg(long)]
-        asset_id: Option<String>,
-        #[arg(long)]
-        amount: u64,
+        /// Symbol or ID of the asset. Defaults to the base asset.
+        ///
+        /// Without --amount give it as `<asset>:<amount>`, and repeat to fund several
+        /// assets in one transaction.
+        #[arg(long, alias = "asset-id", conflicts_with = "all_except_gas")]
+        asset: Vec<String>,
+        /// In whole units if the asset is registered, base units otherwise
+        #[arg(long, required_unless_present_any = ["asset", "all_except_gas"])]
+        amount: Option<String>,
+        /// Move the whole wallet, keeping only enough of the base asset for the fee
+        #[arg(long, conflicts_with = "amount")]
+        all_except_gas: bool,
     },
+    /// Spend from the wallet's predicate
     SendTo {
+        /// Address, `contract:<hex>` or address book name
         #[arg(long)]
         recipient: String,
+        /// Symbol or ID of the asset. Defaults to the base asset.
+        #[arg(long, alias = "asset-id")]
+        asset: Option<String>,
+        /// In whole units if the asset is registered, base units otherwise
         #[arg(long)]
-        asset_id: Option<String>,
-        #[arg(long)]
-        amount: u64,
+        amount: String,
     },
+    /// Claim funds from someone's predicate, with the wallet as recipient
     Claim {
+        /// Owner of the predicate, as an address or address book name
         #[arg(long)]
         owner: String,
+        /// Symbol or ID of the asset. Defaults to the base asset.
+        #[arg(long, alias = "asset-id")]
+        asset: Option<String>,
+        /// In whole units if the asset is registered, base units otherwise
         #[arg(long)]
-        asset_id: Option<String>,
-        #[arg(long)]
-        amount: u64,
+        amount: String,
     },
+    /// Cancel claims against the wallet during their challenge period
     Disprove {
+        /// ID of the claim. Repeat to disprove several claims in one transaction
+        #[arg(long, required = true)]
+        claim_id: Vec<u64>,
+        /// Contract holding the claims. Looked up in the registry if not provided.
         #[arg(long)]
-        claim_id: u64,
+        in_contract: Option<String>,
     },
+    /// Pay out a claim once its challenge period is over
     Fulfill {
+        /// ID of the claim
         #[arg(long)]
         claim_id: u64,
+        /// Owner the claim was filed against, as an address or address book name.
+        /// Used to look the claim up in the registry.
+        #[arg(long, required_unless_present = "in_contract")]
+        owner: Option<String>,
+        /// Contract holding the claim
+        #[arg(long)]
+        in_contract: Option<String>,
+    },
+    /// List predicate addresses for all known artifact versions
+    Versions,
+    /// Move funds from previous predicate versions to the current one
+    Migrate,
+    /// Snapshot the local debug builds as a new current version
+    Pin {
+        /// Name of the new version, e.g. `v2`
+        #[arg(long)]
+        name: String,
+    },
+    /// Serve the HTTP/JSON API for the wallet app
+    Serve {
+        /// Address to listen on
+        #[arg(long, env = "KPOP_ADDR", default_value = "127.0.0.1:8080")]
+        addr: std::net::SocketAddr,
+        /// Bearer token for the endpoints that sign with the wallet. Without it only
+        /// the read endpoints and the unsigned flow are served.
+        #[arg(long, env = "KPOP_API_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+    /// Watch for claim activity and send notifications
+    Monitor(MonitorArgs),
+    /// Full-screen dashboard of balances and claims
+    Tui {
+        /// Owner to look for claims filed by the wallet. Everyone in the address
+        /// book is checked as well.
+        #[arg(long)]
+        owner: Vec<String>,
+    },
+    /// Run the operations listed in a JSON or CSV file
+    Batch(BatchArgs),
+    /// Manage the registry of known claims contracts
+    Contracts {
+        #[command(subcommand)]
+        action: ContractsAction,
+    },
+    /// Manage names for addresses and contracts
+    Contacts {
+        #[command(subcommand)]
+        action: ContactsAction,
+    },
+    /// Manage asset symbols and decimals used for amounts
+    Assets {
+        #[command(subcommand)]
+        action: AssetsAction,
+    },
+    /// Spend from a predicate whose owner key is kept offline
+    Tx {
+        #[command(subcommand)]
+        action: TxAction,
+    },
+    /// Control a node started in debug mode, e.g. by `kpop devnet`
+    Node {
+        #[command(subcommand)]
+        action: NodeAction,
+    },
+    /// Run a local node with funded wallets and the claims contract deployed
+    #[cfg(feature = "devnet")]
+    Devnet(DevnetArgs),
+    /// Print a shell completion script, e.g. `kpop completions bash > /etc/bash_completion.d/kpop`
+    Completions {
+        /// Shell to complete for
+        shell: clap_complete::Shell,
+    },
+    /// Write man pages for kpop and every subcommand
+    Man {
+        /// Directory to write the pages to
+        #[arg(long, default_value = "man")]
+        out_dir: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum TxAction {
+    /// Write an unsigned spend from the owner's predicate to a file (online)
+    Build {
+        /// Address or address book name
+        #[arg(long)]
+        owner: String,
+        /// Address or address book name
+        #[arg(long)]
+        recipient: String,
+        /// Symbol or ID of the asset. Defaults to the base asset.
+        #[arg(long, alias = "asset-id")]
+        asset: Option<String>,
+        /// In whole units if the asset is registered, base units otherwise
+        #[arg(long)]
+        amount: String,
+        /// Where to write the unsigned transaction
+        #[arg(long, default_value = "unsigned-tx.json")]
+        out: std::path::PathBuf,
+    },
+    /// Add the owner signature with --private-key (offline)
+    Sign {
+        /// Unsigned transaction written by `tx build`
+        #[arg(long)]
+        file: std::path::PathBuf,
+        /// Where to write the signed transaction. Defaults to overwriting --file.
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+    },
+    /// Submit a signed transaction file (online)
+    Submit {
+        /// Transaction signed by `tx sign`
+        #[arg(long)]
+        file: std::path::PathBuf,
+    },
+    /// Wait for a transaction submitted with --no-wait
+    Wait {
+        /// ID printed when the transaction was submitted
+        tx_id: String,
+        /// Seconds to wait for inclusion
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+    },
+    /// Look up fee, block and logs of any past transaction
+    Status {
+        /// ID of the transaction
+        tx_id: String,
+    },
+}
+
+#[cfg(feature = "devnet")]
+#[derive(clap::Args)]
+#[command(args_conflicts_with_subcommands = true)]
+struct DevnetArgs {
+    #[command(subcommand)]
+    action: Option<DevnetAction>,
+    /// Address for the node to listen on
+    #[arg(long, default_value = kpop::devnet::DEFAULT_DEVNET_ADDR)]
+    addr: std::net::SocketAddr,
+    /// Where to write the profile for the node
+    #[arg(long, default_value = kpop::devnet::DEFAULT_PROFILE_PATH)]
+    out: std::path::PathBuf,
+}
+
+#[cfg(feature = "devnet")]
+#[derive(Subcommand)]
+enum DevnetAction {
+    /// Produce empty blocks on the devnet, e.g. to get past the challenge period
+    Advance {
+        /// Number of blocks to produce
+        #[arg(long, default_value_t = kpop::CHALLENGE_PERIOD)]
+        blocks: u32,
+    },
+}
+
+#[derive(Subcommand)]
+enum NodeAction {
+    /// Produce empty blocks to move claims through their challenge period
+    ProduceBlocks {
+        /// Number of blocks to produce
+        #[arg(
+            long,
+            required_unless_present = "until_claim_fulfillable",
+            conflicts_with = "until_claim_fulfillable"
+        )]
+        count: Option<u32>,
+        /// Produce just enough blocks for this claim to be fulfilled
+        #[arg(long)]
+        until_claim_fulfillable: Option<u64>,
+        /// Owner the claim was made against, as an address or address book name.
+        /// Defaults to the wallet.
+        #[arg(long, requires = "until_claim_fulfillable")]
+        owner: Option<String>,
+        /// Contract holding the claim. Looked up in the registry if not provided.
+        #[arg(long, requires = "until_claim_fulfillable")]
+        in_contract: Option<String>,
+    },
+}
+
+#[derive(clap::Args)]
+struct BatchArgs {
+    /// JSON array or CSV file of operations, see `kpop::batch` for the columns
+    file: std::path::PathBuf,
+    /// Operations to run at the same time
+    #[arg(long, default_value_t = 1)]
+    concurrency: usize,
+    /// Retries for operations that fail before submission or are squeezed out
+    #[arg(long, default_value_t = 2)]
+    retries: u32,
+    /// Seconds before the first retry, doubled for every further one
+    #[arg(long, default_value_t = 1)]
+    retry_delay: u64,
+    /// Where to write per-row results, as CSV if it ends in .csv
+    #[arg(long, default_value = "batch-report.json")]
+    report: std::path::PathBuf,
+}
+
+#[derive(clap::Args)]
+struct MonitorArgs {
+    /// Owner to watch. Defaults to the wallet.
+    #[arg(long)]
+    owner: Option<String>,
+    /// Seconds between checks for new blocks
+    #[arg(long, default_value_t = 1)]
+    poll_interval: u64,
+    /// URL to POST claim events to as JSON
+    #[arg(long)]
+    webhook: Vec<String>,
+    /// Shell command to run with the claim event as JSON on stdin
+    #[arg(long)]
+    command: Vec<String>,
+    /// Email address to notify
+    #[arg(long)]
+    email_to: Option<String>,
+    /// Sender of notification emails
+    #[arg(long)]
+    email_from: Option<String>,
+    /// SMTP server to send email through
+    #[arg(long, env)]
+    smtp_host: Option<String>,
+    /// Port of the SMTP server
+    #[arg(long, env, default_value_t = 587)]
+    smtp_port: u16,
+    /// SMTP login, used together with --smtp-password
+    #[arg(long, env)]
+    smtp_user: Option<String>,
+    /// SMTP password, used together with --smtp-user
+    #[arg(long, env)]
+    smtp_password: Option<String>,
+    /// Talk plain SMTP, e.g. to a relay on localhost
+    #[arg(long)]
+    no_starttls: bool,
+}
+
+#[derive(Subcommand)]
+enum ContractsAction {
+    /// List known contracts, marking the active one
+    List,
+    /// Remember a contract, e.g. one deployed by someone else
+    Add {
+        /// ID of the claims contract
+        #[arg(long)]
+        contract_id: String,
+    },
+    /// Forget a contract
+    Remove {
+        /// ID of the claims contract
+        #[arg(long)]
+        contract_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AssetsAction {
+    /// List registered assets
+    List,
+    /// Register an asset, replacing any with the same symbol
+    Add {
+        /// Name to use in place of the asset ID, e.g. `ETH`
+        #[arg(long)]
+        symbol: String,
+        /// ID of the asset, hex with or without 0x
+        #[arg(long)]
+        asset_id: String,
+        /// Decimal places of one whole unit, e.g. 9 for ETH
+        #[arg(long)]
+        decimals: u8,
+    },
+    /// Unregister an asset
+    Remove {
+        /// Symbol the asset was registered with
+        #[arg(long)]
+        symbol: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContactsAction {
+    /// List address book entries
+    List,
+    /// Name an address or contract, replacing any entry with the same name
+    Add {
+        /// Name to use in place of the address
+        #[arg(long)]
+        name: String,
+        /// Address, `contract:<hex>` or the name of another entry
+        #[arg(long)]
+        address: String,
+    },
+    /// Remove an entry
+    Remove {
+        /// Name of the entry
+        #[arg(long)]
+        name: String,
     },
 }
@@ -0,0 +1,28 @@
+//! Driving a node started in debug mode, e.g. by `kpop devnet`.
+
+use fuels::prelude::*;
+
+use crate::{claims, Claim};
+
+/// Produce `blocks` empty blocks and return the new height. Only nodes started
+/// in debug mode allow this.
+pub async fn advance(provider: &Provider, blocks: u32) -> u32 {
+    provider
+        .produce_blocks(blocks, None)
+        .await
+        .expect("should be able to produce blocks, is the node in debug mode?")
+}
+
+/// Produce just enough blocks for `claim` to be fulfilled and return the new
+/// height. Nothing is produced if it already can be.
+pub async fn advance_until_fulfillable(provider: &Provider, claim: &Claim) -> u32 {
+    let height = provider
+        .latest_block_height()
+        .await
+        .expect("should be able to get block height");
+
+    match claims::blocks_left(claim, height) {
+        0 => height,
+        blocks => advance(provider, blocks).await,
+    }
+}
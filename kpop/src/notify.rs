@@ -0,0 +1,151 @@
+//! Notification sinks for claim activity.
+//!
+//! An owner has [`CHALLENGE_PERIOD`](crate::CHALLENGE_PERIOD) blocks to disprove a
+//! claim, so [`monitor`] forwards claim events from [`Kpop::watch`] to every
+//! configured [`Notifier`]. A failing sink is reported and doesn't stop the others.
+
+use std::{process::Stdio, time::Duration};
+
+use async_trait::async_trait;
+use fuels::prelude::*;
+use futures::StreamExt;
+use lettre::{
+    message::header::ContentType, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+    AsyncTransport, Message, Tokio1Executor,
+};
+use tokio::io::AsyncWriteExt;
+
+use crate::{watch::ClaimEvent, Kpop};
+
+pub type NotifyResult = std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    async fn notify(&self, event: &ClaimEvent) -> NotifyResult;
+}
+
+/// POSTs the event as JSON to `url`.
+#[derive(Debug, Clone)]
+pub struct WebhookNotifier {
+    pub url: String,
+    client: reqwest::Client,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    async fn notify(&self, event: &ClaimEvent) -> NotifyResult {
+        self.client
+            .post(&self.url)
+            .json(event)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+}
+
+/// Sends the event as JSON in the body of an email.
+#[derive(Debug, Clone)]
+pub struct EmailNotifier {
+    pub host: String,
+    pub port: u16,
+    pub from: String,
+    pub to: String,
+    pub credentials: Option<(String, String)>,
+    /// Use STARTTLS. Only disable this for a relay on the local machine.
+    pub starttls: bool,
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    async fn notify(&self, event: &ClaimEvent) -> NotifyResult {
+        let message = Message::builder()
+            .from(self.from.parse()?)
+            .to(self.to.parse()?)
+            .subject(format!("kpop: claim {}", event.name()))
+            .header(ContentType::TEXT_PLAIN)
+            .body(serde_json::to_string_pretty(event)?)?;
+
+        let mut builder = if self.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&self.host)?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&self.host)
+        }
+        .port(self.port);
+
+        if let Some((user, password)) = &self.credentials {
+            builder = builder.credentials(Credentials::new(user.clone(), password.clone()));
+        }
+
+        builder.build().send(message).await?;
+
+        Ok(())
+    }
+}
+
+/// Runs `command` with `sh -c`, passing the event as JSON on stdin.
+#[derive(Debug, Clone)]
+pub struct CommandNotifier {
+    pub command: String,
+}
+
+#[async_trait]
+impl Notifier for CommandNotifier {
+    async fn notify(&self, event: &ClaimEvent) -> NotifyResult {
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .spawn()?;
+
+        let mut stdin = child.stdin.take().expect("stdin should be piped");
+        stdin.write_all(&serde_json::to_vec(event)?).await?;
+        drop(stdin);
+
+        let status = child.wait().await?;
+        if !status.success() {
+            return Err(format!("`{}` exited with {status}", self.command).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Dispatch claim events for `owner` to `notifiers` until the stream ends.
+///
+/// Predicate balance changes are not forwarded, and node errors are only printed.
+pub async fn monitor(
+    kp: &Kpop,
+    owner: Address,
+    poll_interval: Duration,
+    notifiers: &[Box<dyn Notifier>],
+) {
+    let mut events = Box::pin(kp.watch(owner, poll_interval));
+
+    while let Some(event) = events.next().await {
+        match &event {
+            ClaimEvent::PredicateBalance { .. } => continue,
+            ClaimEvent::Error { message } => {
+                eprintln!("Failed to watch claims: {message}");
+                continue;
+            }
+            _ => {}
+        }
+
+        for notifier in notifiers {
+            if let Err(err) = notifier.notify(&event).await {
+                eprintln!("Failed to send notification: {err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! What a submitted transaction did, read back from the node.
+
+use fuels::{
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::{Receipt, TxId},
+    types::{errors::transaction::Reason, tx_status::TxStatus},
+};
+
+#[derive(Debug, Clone)]
+pub struct OperationOutcome {
+    pub tx_id: TxId,
+    /// `None` while the transaction hasn't been included in a block.
+    pub block_height: Option<u32>,
+    pub total_fee: u64,
+    pub gas_used: u64,
+    pub logs: Vec<String>,
+    /// Claims filed, disproved or fulfilled by the operation.
+    pub claim_ids: Vec<u64>,
+    pub receipts: Vec<Receipt>,
+    pub revert_reason: Option<String>,
+}
+
+impl OperationOutcome {
+    /// Look up `tx_id`, or `None` if the node doesn't know it.
+    ///
+    /// Logs and revert reasons are decoded with `log_decoder` when one is given.
+    pub(crate) async fn fetch(
+        provider: &Provider,
+        tx_id: TxId,
+        log_decoder: Option<&LogDecoder>,
+    ) -> Result<Option<Self>> {
+        let Some(response) = provider.get_transaction_by_id(&tx_id).await? else {
+            return Ok(None);
+        };
+
+        let block_height = match response.block_id {
+            Some(block_id) => provider
+                .block(&block_id)
+                .await?
+                .map(|block| block.header.height),
+            None => None,
+        };
+
+        let (gas_used, total_fee) = match &response.status {
+            TxStatus::Success {
+                total_gas,
+                total_fee,
+                ..
+            }
+            | TxStatus::Revert {
+                total_gas,
+                total_fee,
+                ..
+            } => (*total_gas, *total_fee),
+            _ => (0, 0),
+        };
+
+        let receipts = response.status.clone().take_receipts();
+        let logs = log_decoder
+            .map(|log_decoder| log_decoder.decode_logs(&receipts).filter_succeeded())
+            .unwrap_or_default();
+
+        let revert_reason = match response.status.take_receipts_checked(log_decoder) {
+            Ok(_) => None,
+            Err(Error::Transaction(Reason::Reverted { reason, .. })) => Some(reason),
+            Err(err) => Some(err.to_string()),
+        };
+
+        Ok(Some(Self {
+            tx_id,
+            block_height,
+            total_fee,
+            gas_used,
+            logs,
+            claim_ids: Vec::new(),
+            receipts,
+            revert_reason,
+        }))
+    }
+
+    pub(crate) fn with_claim_ids(self, claim_ids: Vec<u64>) -> Self {
+        Self { claim_ids, ..self }
+    }
+}
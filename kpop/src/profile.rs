@@ -0,0 +1,31 @@
+//! Connection settings kept in a file, so they don't have to be repeated.
+//!
+//! Anything given on the command line or in the environment takes precedence
+//! over the profile. `kpop devnet` writes one for the node it starts.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contract_id: Option<String>,
+}
+
+impl Profile {
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let contents = std::fs::read_to_string(path).expect("should be able to read profile");
+        serde_json::from_str(&contents).expect("profile should be valid json")
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) {
+        let contents =
+            serde_json::to_string_pretty(self).expect("should be able to encode profile");
+        std::fs::write(path, contents).expect("should be able to write profile");
+    }
+}
@@ -0,0 +1,82 @@
+//! Known claims contracts.
+//!
+//! A claim is filed in whichever contract the claimant's make-claim script was
+//! configured with, so an owner may have pending claims in several contracts
+//! deployed over time. The registry keeps track of all of them.
+
+use std::{path::PathBuf, str::FromStr};
+
+use fuels::types::ContractId;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_REGISTRY_PATH: &str = "./contracts.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    contracts: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Registry {
+    path: PathBuf,
+    contracts: Vec<ContractId>,
+}
+
+impl Registry {
+    /// Load the registry at `path`. A missing file is an empty registry.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        let file: RegistryFile = if path.exists() {
+            let contents = std::fs::read_to_string(&path).expect("should be able to read registry");
+            serde_json::from_str(&contents).expect("registry should be valid json")
+        } else {
+            RegistryFile::default()
+        };
+
+        let contracts = file
+            .contracts
+            .iter()
+            .map(|id| ContractId::from_str(id).expect("registry should contain valid ids"))
+            .collect();
+
+        Self { path, contracts }
+    }
+
+    pub fn contracts(&self) -> &[ContractId] {
+        &self.contracts
+    }
+
+    /// Returns false if the contract was already known.
+    pub fn add(&mut self, contract_id: ContractId) -> bool {
+        if self.contracts.contains(&contract_id) {
+            return false;
+        }
+
+        self.contracts.push(contract_id);
+        true
+    }
+
+    /// Returns false if the contract wasn't known.
+    pub fn remove(&mut self, contract_id: &ContractId) -> bool {
+        let len = self.contracts.len();
+        self.contracts.retain(|id| id != contract_id);
+        self.contracts.len() != len
+    }
+
+    pub fn save(&self) {
+        let file = RegistryFile {
+            contracts: self.contracts.iter().map(|id| id.to_string()).collect(),
+        };
+
+        let contents =
+            serde_json::to_string_pretty(&file).expect("should be able to encode registry");
+        std::fs::write(&self.path, contents).expect("should be able to write registry");
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::load(DEFAULT_REGISTRY_PATH)
+    }
+}
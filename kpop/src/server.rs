@@ -0,0 +1,406 @@
+//! HTTP/JSON API around [`Kpop`] for the wallet app.
+//!
+//! The endpoints mirror the CLI actions. Spends from a predicate can also be
+//! built unsigned for a client-provided owner, signed client side, and handed
+//! back through `/submit`. See `openapi.json` for the full description.
+//!
+//! Endpoints that sign with the server's wallet require a bearer token. Without
+//! one they aren't routed, so only reads and the unsigned flow are available.
+
+use std::{collections::HashMap, net::SocketAddr, str::FromStr, sync::Arc};
+
+use axum::{
+    extract::{Query, Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{get, post, MethodRouter},
+    Json, Router,
+};
+use fuels::{prelude::*, tx::TxId, types::Identity};
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    address, claims::ClaimView, error::TxError, outcome::OperationOutcome,
+    unsigned::UnsignedTransaction, watch, Kpop,
+};
+
+pub const OPENAPI: &str = include_str!("../openapi.json");
+
+/// Paths routed whether or not a token is given. `openapi.json` describes
+/// exactly these and [`SIGNING_PATHS`].
+pub const PUBLIC_PATHS: [&str; 6] = [
+    "/openapi.json",
+    "/balances",
+    "/claims",
+    "/events",
+    "/send/unsigned",
+    "/submit",
+];
+
+/// Paths that sign with the server's wallet.
+pub const SIGNING_PATHS: [&str; 5] = ["/fund", "/send", "/claim", "/disprove", "/fulfill"];
+
+fn handler(path: &str) -> MethodRouter<Arc<Kpop>> {
+    match path {
+        "/openapi.json" => get(openapi),
+        "/balances" => get(balances),
+        "/claims" => get(claims),
+        "/events" => get(events),
+        "/send/unsigned" => post(send_unsigned),
+        "/submit" => post(submit),
+        "/fund" => post(fund),
+        "/send" => post(send),
+        "/claim" => post(claim),
+        "/disprove" => post(disprove),
+        "/fulfill" => post(fulfill),
+        _ => unreachable!("{path} should have a handler"),
+    }
+}
+
+/// Signing endpoints are only routed when a `token` is given, and then require
+/// it as a bearer token.
+pub fn router(kp: Kpop, token: Option<String>) -> Router {
+    let mut router = PUBLIC_PATHS.iter().fold(Router::new(), |router, path| {
+        router.route(path, handler(path))
+    });
+
+    if let Some(token) = token {
+        let signing = SIGNING_PATHS
+            .iter()
+            .fold(Router::new(), |router, path| {
+                router.route(path, handler(path))
+            })
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(token),
+                require_token,
+            ));
+        router = router.merge(signing);
+    }
+
+    router.with_state(Arc::new(kp))
+}
+
+pub async fn serve(kp: Kpop, addr: SocketAddr, token: Option<String>) {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .expect("should be able to bind server address");
+
+    axum::serve(listener, router(kp, token))
+        .await
+        .expect("server should keep running");
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balances {
+    pub wallet_address: String,
+    pub wallet: HashMap<String, u64>,
+    pub predicate_address: String,
+    pub predicate: HashMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventsQuery {
+    pub owner: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundRequest {
+    pub asset_id: Option<String>,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendRequest {
+    pub recipient: String,
+    pub asset_id: Option<String>,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedSendRequest {
+    pub owner: String,
+    pub recipient: String,
+    pub asset_id: Option<String>,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimRequest {
+    pub owner: String,
+    pub asset_id: Option<String>,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimIdRequest {
+    pub claim_id: u64,
+    pub contract_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxResponse {
+    pub tx_id: String,
+    pub block_height: Option<u32>,
+    pub total_fee: u64,
+    pub gas_used: u64,
+}
+
+impl From<OperationOutcome> for TxResponse {
+    fn from(outcome: OperationOutcome) -> Self {
+        Self {
+            tx_id: outcome.tx_id.to_string(),
+            block_height: outcome.block_height,
+            total_fee: outcome.total_fee,
+            gas_used: outcome.gas_used,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimResponse {
+    pub claim_id: u64,
+    #[serde(flatten)]
+    pub tx: TxResponse,
+}
+
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+    tx_id: Option<TxId>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            tx_id: None,
+        }
+    }
+
+    fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, message)
+    }
+}
+
+/// The node failed to answer a read, e.g. of balances or claims.
+impl From<Error> for ApiError {
+    fn from(err: Error) -> Self {
+        Self::new(StatusCode::BAD_GATEWAY, format!("node error: {err}"))
+    }
+}
+
+impl From<TxError> for ApiError {
+    fn from(err: TxError) -> Self {
+        let status = match err {
+            TxError::NotSubmitted { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            TxError::Rejected { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            TxError::SqueezedOut { .. } | TxError::Reverted { .. } => StatusCode::CONFLICT,
+            TxError::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+        };
+
+        Self {
+            tx_id: err.tx_id(),
+            ..Self::new(status, err.to_string())
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut body = serde_json::json!({ "error": self.message });
+        if let Some(tx_id) = self.tx_id {
+            body["tx_id"] = tx_id.to_string().into();
+        }
+
+        (self.status, Json(body)).into_response()
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+async fn require_token(
+    State(token): State<Arc<String>>,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, ApiError> {
+    let bearer = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    if bearer != Some(token.as_str()) {
+        return Err(ApiError::unauthorized("missing or wrong bearer token"));
+    }
+
+    Ok(next.run(request).await)
+}
+
+fn parse_asset_id(asset_id: Option<String>) -> std::result::Result<Option<AssetId>, ApiError> {
+    asset_id
+        .map(|s| AssetId::from_str(&s).map_err(|_| ApiError::bad_request("invalid asset_id")))
+        .transpose()
+}
+
+fn parse_address(kp: &Kpop, address: &str, field: &str) -> std::result::Result<Address, ApiError> {
+    address::parse_address(address, &kp.address_book)
+        .map_err(|err| ApiError::bad_request(format!("{field}: {err}")))
+}
+
+fn parse_identity(
+    kp: &Kpop,
+    identity: &str,
+    field: &str,
+) -> std::result::Result<Identity, ApiError> {
+    address::parse_identity(identity, &kp.address_book)
+        .map_err(|err| ApiError::bad_request(format!("{field}: {err}")))
+}
+
+fn parse_contract_id(
+    kp: &Kpop,
+    contract_id: Option<String>,
+) -> std::result::Result<ContractId, ApiError> {
+    match contract_id {
+        Some(s) => {
+            ContractId::from_str(&s).map_err(|_| ApiError::bad_request("invalid contract_id"))
+        }
+        None => Ok(kp.contract_id),
+    }
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([("content-type", "application/json")], OPENAPI)
+}
+
+async fn balances(State(kp): State<Arc<Kpop>>) -> ApiResult<Balances> {
+    let owner = kp.wallet.address().into();
+
+    Ok(Json(Balances {
+        wallet_address: kp.wallet.address().to_string(),
+        wallet: kp.try_wallet_balance().await?,
+        predicate_address: kp.predicate_address().await.to_string(),
+        predicate: kp.try_predicate_balance_of(owner).await?,
+    }))
+}
+
+async fn claims(State(kp): State<Arc<Kpop>>) -> ApiResult<Vec<ClaimView>> {
+    let claims = kp.try_get_all_claims_of(kp.wallet.address().into()).await?;
+
+    Ok(Json(
+        claims
+            .into_iter()
+            .map(|(contract_id, claim)| ClaimView::new(contract_id, claim))
+            .collect(),
+    ))
+}
+
+/// Server-sent events with claim activity for `owner`, defaulting to the wallet.
+async fn events(
+    State(kp): State<Arc<Kpop>>,
+    Query(query): Query<EventsQuery>,
+) -> std::result::Result<Sse<impl Stream<Item = std::result::Result<Event, axum::Error>>>, ApiError>
+{
+    let owner = match query.owner {
+        Some(owner) => parse_address(&kp, &owner, "owner")?,
+        None => kp.wallet.address().into(),
+    };
+
+    let stream = kp
+        .watch(owner, watch::DEFAULT_POLL_INTERVAL)
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+async fn fund(State(kp): State<Arc<Kpop>>, Json(req): Json<FundRequest>) -> ApiResult<TxResponse> {
+    let asset_id = parse_asset_id(req.asset_id)?;
+
+    let outcome = kp.fund_predicate(asset_id, req.amount).await?;
+
+    Ok(Json(outcome.into()))
+}
+
+async fn send(State(kp): State<Arc<Kpop>>, Json(req): Json<SendRequest>) -> ApiResult<TxResponse> {
+    let recipient = parse_identity(&kp, &req.recipient, "recipient")?;
+    let asset_id = parse_asset_id(req.asset_id)?;
+
+    let outcome = kp.send_to(&recipient, asset_id, req.amount).await?;
+
+    Ok(Json(outcome.into()))
+}
+
+async fn send_unsigned(
+    State(kp): State<Arc<Kpop>>,
+    Json(req): Json<UnsignedSendRequest>,
+) -> ApiResult<UnsignedTransaction> {
+    let owner = parse_address(&kp, &req.owner, "owner")?;
+    let recipient = Bech32Address::from(parse_address(&kp, &req.recipient, "recipient")?);
+    let asset_id = parse_asset_id(req.asset_id)?;
+
+    let unsigned = kp
+        .build_unsigned_send_to(owner, &recipient, asset_id, req.amount)
+        .await?;
+
+    Ok(Json(unsigned))
+}
+
+async fn submit(
+    State(kp): State<Arc<Kpop>>,
+    Json(signed): Json<UnsignedTransaction>,
+) -> ApiResult<TxResponse> {
+    if !signed.is_signed() {
+        return Err(ApiError::bad_request("transaction has not been signed"));
+    }
+
+    let outcome = kp.submit_signed(signed).await?;
+
+    Ok(Json(outcome.into()))
+}
+
+async fn claim(
+    State(kp): State<Arc<Kpop>>,
+    Json(req): Json<ClaimRequest>,
+) -> ApiResult<ClaimResponse> {
+    let owner = parse_address(&kp, &req.owner, "owner")?;
+    let asset_id = parse_asset_id(req.asset_id)?;
+
+    let outcome = kp.claim(owner, asset_id, req.amount).await?;
+
+    Ok(Json(ClaimResponse {
+        claim_id: outcome.claim_ids[0],
+        tx: outcome.into(),
+    }))
+}
+
+async fn disprove(
+    State(kp): State<Arc<Kpop>>,
+    Json(req): Json<ClaimIdRequest>,
+) -> ApiResult<TxResponse> {
+    let contract_id = parse_contract_id(&kp, req.contract_id)?;
+
+    let outcome = kp.disprove_claim_in(contract_id, req.claim_id).await?;
+
+    Ok(Json(outcome.into()))
+}
+
+async fn fulfill(
+    State(kp): State<Arc<Kpop>>,
+    Json(req): Json<ClaimIdRequest>,
+) -> ApiResult<TxResponse> {
+    let contract_id = parse_contract_id(&kp, req.contract_id)?;
+
+    let outcome = kp.fulfill_claim_in(contract_id, req.claim_id).await?;
+
+    Ok(Json(outcome.into()))
+}
@@ -0,0 +1,591 @@
+//! Full-screen dashboard for the wallet's predicate and claims.
+//!
+//! Shows the wallet and predicate balances, the claims against the wallet with
+//! the blocks left to disprove them, and the claims the wallet filed as recipient,
+//! which can be fulfilled once their challenge period is over. Claims can only be
+//! looked up by owner, so filed claims are found by checking the owners given to
+//! [`run`]. Everything is reloaded whenever a new block is produced.
+//!
+//! The [`Dashboard`] only holds state and turns keys into [`Command`]s; [`run`]
+//! owns the terminal and carries the commands out with the existing [`Kpop`]
+//! methods.
+
+use std::{io, sync::Arc};
+
+use crossterm::{
+    event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use fuels::prelude::*;
+use futures::StreamExt;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use tokio::sync::{mpsc, Notify};
+
+use crate::{
+    address::AddressBook,
+    assets::{sorted_balance, AssetRegistry},
+    claims::blocks_left,
+    Claim, Kpop,
+};
+
+/// Claims with fewer blocks left to disprove them are highlighted.
+const URGENT_BLOCKS: u32 = 20;
+
+/// Everything the dashboard shows, as of one block.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub height: u32,
+    pub wallet_address: String,
+    pub predicate_address: String,
+    pub wallet_balance: Vec<(AssetId, u64)>,
+    pub predicate_balance: Vec<(AssetId, u64)>,
+    /// Claims against the wallet.
+    pub against: Vec<(ContractId, Claim)>,
+    /// Claims with the wallet as recipient against any of the watched owners.
+    pub filed: Vec<(ContractId, Claim)>,
+}
+
+impl Snapshot {
+    pub async fn load(kp: &Kpop, owners: &[Address]) -> Result<Self> {
+        let wallet: Address = kp.wallet.address().into();
+
+        let mut filed = Vec::new();
+        for owner in owners {
+            filed.extend(
+                kp.try_get_all_claims_of(*owner)
+                    .await?
+                    .into_iter()
+                    .filter(|(_, claim)| claim.recipient == wallet),
+            );
+        }
+
+        Ok(Self {
+            height: kp.wallet.provider().unwrap().latest_block_height().await?,
+            wallet_address: kp.wallet.address().to_string(),
+            predicate_address: kp.predicate_address().await.to_string(),
+            wallet_balance: sorted_balance(kp.try_wallet_balance().await?),
+            predicate_balance: sorted_balance(kp.try_predicate_balance_of(wallet).await?),
+            against: kp.try_get_all_claims_of(wallet).await?,
+            filed,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Against,
+    Filed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Refresh,
+    Disprove {
+        contract_id: ContractId,
+        claim_id: u64,
+    },
+    Fulfill {
+        contract_id: ContractId,
+        claim_id: u64,
+    },
+    /// Fund the predicate, as typed: an amount optionally followed by an asset.
+    Fund(String),
+}
+
+impl Command {
+    fn describe(&self) -> String {
+        match self {
+            Command::Quit | Command::Refresh => String::new(),
+            Command::Disprove { claim_id, .. } => format!("Disproving claim {claim_id}"),
+            Command::Fulfill { claim_id, .. } => format!("Fulfilling claim {claim_id}"),
+            Command::Fund(amount) => format!("Funding {amount}"),
+        }
+    }
+}
+
+pub struct Dashboard {
+    assets: AssetRegistry,
+    address_book: AddressBook,
+    snapshot: Option<Snapshot>,
+    pane: Pane,
+    against: ListState,
+    filed: ListState,
+    /// Text typed for the fund amount, while asking for it.
+    prompt: Option<String>,
+    status: String,
+}
+
+impl Dashboard {
+    pub fn new(assets: AssetRegistry, address_book: AddressBook) -> Self {
+        Self {
+            assets,
+            address_book,
+            snapshot: None,
+            pane: Pane::Against,
+            against: ListState::default(),
+            filed: ListState::default(),
+            prompt: None,
+            status: "Loading...".to_string(),
+        }
+    }
+
+    pub fn snapshot(&self) -> Option<&Snapshot> {
+        self.snapshot.as_ref()
+    }
+
+    pub fn pane(&self) -> Pane {
+        self.pane
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn update(&mut self, snapshot: Snapshot) {
+        if self.snapshot.is_none() {
+            self.status.clear();
+        }
+
+        select_within(&mut self.against, snapshot.against.len());
+        select_within(&mut self.filed, snapshot.filed.len());
+        self.snapshot = Some(snapshot);
+    }
+
+    pub fn set_status(&mut self, status: impl Into<String>) {
+        self.status = status.into();
+    }
+
+    /// The claim under the cursor in the focused pane.
+    pub fn selected(&self) -> Option<&(ContractId, Claim)> {
+        let snapshot = self.snapshot.as_ref()?;
+
+        match self.pane {
+            Pane::Against => snapshot.against.get(self.against.selected()?),
+            Pane::Filed => snapshot.filed.get(self.filed.selected()?),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Command> {
+        if key.kind != KeyEventKind::Press {
+            return None;
+        }
+
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Command::Quit);
+        }
+
+        if let Some(prompt) = &mut self.prompt {
+            match key.code {
+                KeyCode::Enter => {
+                    let amount = prompt.trim().to_string();
+                    self.prompt = None;
+                    return (!amount.is_empty()).then_some(Command::Fund(amount));
+                }
+                KeyCode::Esc => self.prompt = None,
+                KeyCode::Backspace => {
+                    prompt.pop();
+                }
+                KeyCode::Char(c) => prompt.push(c),
+                _ => {}
+            }
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(Command::Quit),
+            KeyCode::Char('r') => Some(Command::Refresh),
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right | KeyCode::Char('h' | 'l') => {
+                self.pane = match self.pane {
+                    Pane::Against => Pane::Filed,
+                    Pane::Filed => Pane::Against,
+                };
+                None
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.focused_list().select_previous();
+                None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.focused_list().select_next();
+                None
+            }
+            KeyCode::Char('a') => {
+                self.prompt = Some(String::new());
+                None
+            }
+            KeyCode::Char('d') => self.disprove(),
+            KeyCode::Char('f') => self.fulfill(),
+            _ => None,
+        }
+    }
+
+    fn disprove(&mut self) -> Option<Command> {
+        if self.pane != Pane::Against {
+            self.set_status("Only claims against you can be disproved");
+            return None;
+        }
+
+        let (contract_id, claim) = self.selected()?;
+        Some(Command::Disprove {
+            contract_id: *contract_id,
+            claim_id: claim.id,
+        })
+    }
+
+    fn fulfill(&mut self) -> Option<Command> {
+        if self.pane != Pane::Filed {
+            self.set_status("Only your own claims can be fulfilled");
+            return None;
+        }
+
+        let height = self.snapshot.as_ref()?.height;
+        let (contract_id, claim) = self.selected()?;
+        let (contract_id, claim_id) = (*contract_id, claim.id);
+
+        match blocks_left(claim, height) {
+            0 => Some(Command::Fulfill {
+                contract_id,
+                claim_id,
+            }),
+            left => {
+                self.set_status(format!(
+                    "Claim {claim_id} can be fulfilled in {left} blocks"
+                ));
+                None
+            }
+        }
+    }
+
+    fn focused_list(&mut self) -> &mut ListState {
+        match self.pane {
+            Pane::Against => &mut self.against,
+            Pane::Filed => &mut self.filed,
+        }
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [header, balances, claims, status, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(6),
+            Constraint::Min(4),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let Some(snapshot) = self.snapshot.take() else {
+            frame.render_widget(Paragraph::new(self.status.as_str()), header);
+            return;
+        };
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(format!("Wallet:    {}", snapshot.wallet_address)),
+                Line::from(format!("Predicate: {}", snapshot.predicate_address)),
+                Line::from(format!("Block:     {}", snapshot.height)),
+            ]),
+            header,
+        );
+
+        let [wallet, predicate] =
+            Layout::horizontal([Constraint::Percentage(50); 2]).areas(balances);
+        self.render_balance(frame, wallet, "Wallet", &snapshot.wallet_balance);
+        self.render_balance(frame, predicate, "Predicate", &snapshot.predicate_balance);
+
+        let [against, filed] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(claims);
+        self.render_claims(frame, against, Pane::Against, &snapshot);
+        self.render_claims(frame, filed, Pane::Filed, &snapshot);
+
+        let status_line = match &self.prompt {
+            Some(prompt) => {
+                Line::from(format!("Fund amount, e.g. `1.5` or `1.5 ETH`: {prompt}_")).yellow()
+            }
+            None => Line::from(self.status.as_str()),
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+
+        frame.render_widget(
+            Paragraph::new(
+                "q quit  tab switch  ↑↓ select  d disprove  f fulfill  a fund  r refresh",
+            )
+            .dark_gray(),
+            help,
+        );
+
+        self.snapshot = Some(snapshot);
+    }
+
+    fn render_balance(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        balance: &[(AssetId, u64)],
+    ) {
+        let items = balance
+            .iter()
+            .map(|(asset_id, amount)| ListItem::new(self.assets.display(asset_id, *amount)))
+            .collect::<Vec<_>>();
+
+        frame.render_widget(List::new(items).block(Block::bordered().title(title)), area);
+    }
+
+    fn render_claims(&mut self, frame: &mut Frame, area: Rect, pane: Pane, snapshot: &Snapshot) {
+        let (title, claims) = match pane {
+            Pane::Against => ("Claims against you", &snapshot.against),
+            Pane::Filed => ("Your claims", &snapshot.filed),
+        };
+
+        let items =
+            claims
+                .iter()
+                .map(|(_, claim)| {
+                    let left = blocks_left(claim, snapshot.height);
+                    let amount = self.assets.display(&claim.asset, claim.amount);
+
+                    match pane {
+                        Pane::Against => {
+                            let text = format!(
+                                "#{} {}: {amount} to {}",
+                                claim.id,
+                                match left {
+                                    0 => "challenge period over, disprovable until fulfilled"
+                                        .to_string(),
+                                    left => format!("{left} blocks left to disprove"),
+                                },
+                                self.address_book.display_address(&claim.recipient)
+                            );
+                            let style = if left < URGENT_BLOCKS {
+                                Style::new().fg(Color::Red)
+                            } else {
+                                Style::new()
+                            };
+                            ListItem::new(text).style(style)
+                        }
+                        Pane::Filed => {
+                            let text = format!(
+                                "#{} {}: {amount} from {}",
+                                claim.id,
+                                match left {
+                                    0 => "ready, press f to fulfill".to_string(),
+                                    left => format!("fulfillable in {left} blocks"),
+                                },
+                                self.address_book.display_address(&claim.owner)
+                            );
+                            let style = if left == 0 {
+                                Style::new().fg(Color::Green)
+                            } else {
+                                Style::new()
+                            };
+                            ListItem::new(text).style(style)
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+        let mut block = Block::bordered().title(title);
+        if pane == self.pane {
+            block = block.border_style(Style::new().fg(Color::Cyan));
+        }
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        let state = match pane {
+            Pane::Against => &mut self.against,
+            Pane::Filed => &mut self.filed,
+        };
+        frame.render_stateful_widget(list, area, state);
+    }
+}
+
+fn select_within(state: &mut ListState, len: usize) {
+    match (state.selected(), len) {
+        (_, 0) => state.select(None),
+        (None, _) => state.select(Some(0)),
+        (Some(i), len) if i >= len => state.select(Some(len - 1)),
+        _ => {}
+    }
+}
+
+enum Message {
+    Snapshot(Snapshot),
+    Status(String),
+}
+
+/// Run the dashboard until the user quits, also looking for claims filed by the
+/// wallet against `owners`.
+pub async fn run(kp: Kpop, owners: Vec<Address>) -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let res = event_loop(&mut terminal, kp, owners).await;
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    res
+}
+
+async fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    kp: Kpop,
+    owners: Vec<Address>,
+) -> io::Result<()> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let refresh = Arc::new(Notify::new());
+
+    let refresher = tokio::spawn(refresh_on_new_blocks(
+        kp.clone(),
+        owners,
+        refresh.clone(),
+        tx.clone(),
+    ));
+
+    let mut dashboard = Dashboard::new(kp.assets.clone(), kp.address_book.clone());
+    let mut events = EventStream::new();
+
+    loop {
+        terminal.draw(|frame| dashboard.render(frame))?;
+
+        tokio::select! {
+            Some(message) = rx.recv() => match message {
+                Message::Snapshot(snapshot) => dashboard.update(snapshot),
+                Message::Status(status) => dashboard.set_status(status),
+            },
+            Some(event) = events.next() => {
+                let Event::Key(key) = event? else {
+                    continue;
+                };
+
+                match dashboard.handle_key(key) {
+                    None => {}
+                    Some(Command::Quit) => break,
+                    Some(Command::Refresh) => refresh.notify_one(),
+                    Some(command) => {
+                        dashboard.set_status(format!("{}...", command.describe()));
+                        tokio::spawn(execute_command(
+                            kp.clone(),
+                            command,
+                            refresh.clone(),
+                            tx.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    refresher.abort();
+    Ok(())
+}
+
+async fn refresh_on_new_blocks(
+    kp: Kpop,
+    owners: Vec<Address>,
+    refresh: Arc<Notify>,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    let provider = kp.wallet.provider().unwrap().clone();
+    let mut height = None;
+
+    loop {
+        let latest = provider.latest_block_height().await.ok();
+
+        if latest != height {
+            match Snapshot::load(&kp, &owners).await {
+                Ok(snapshot) => {
+                    height = Some(snapshot.height);
+                    if tx.send(Message::Snapshot(snapshot)).is_err() {
+                        return;
+                    }
+                }
+                Err(err) => {
+                    let _ = tx.send(Message::Status(format!("Refresh failed: {err}")));
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(crate::watch::DEFAULT_POLL_INTERVAL) => {}
+            _ = refresh.notified() => height = None,
+        }
+    }
+}
+
+async fn execute_command(
+    kp: Kpop,
+    command: Command,
+    refresh: Arc<Notify>,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    let description = command.describe();
+
+    let status = match run_command(&kp, command).await {
+        Ok(status) => status,
+        Err(err) => format!("{description} failed: {err}"),
+    };
+
+    let _ = tx.send(Message::Status(status));
+    refresh.notify_one();
+}
+
+async fn run_command(kp: &Kpop, command: Command) -> std::result::Result<String, String> {
+    let outcome = match command {
+        Command::Quit | Command::Refresh => unreachable!("handled by the event loop"),
+        Command::Disprove {
+            contract_id,
+            claim_id,
+        } => kp
+            .disprove_claim_in(contract_id, claim_id)
+            .await
+            .map_err(|err| err.to_string())?,
+        Command::Fulfill {
+            contract_id,
+            claim_id,
+        } => kp
+            .fulfill_claim_in(contract_id, claim_id)
+            .await
+            .map_err(|err| err.to_string())?,
+        Command::Fund(amount) => {
+            let (asset_id, amount) = parse_fund(kp, &amount)?;
+            kp.fund_predicate(Some(asset_id), amount)
+                .await
+                .map_err(|err| err.to_string())?
+        }
+    };
+
+    match outcome.revert_reason {
+        Some(reason) => Err(format!("reverted: {reason}")),
+        None => Ok(format!("Done in {}", outcome.tx_id)),
+    }
+}
+
+/// `<amount> [asset]`, in the base asset if no asset is given.
+fn parse_fund(kp: &Kpop, s: &str) -> std::result::Result<(AssetId, u64), String> {
+    let mut parts = s.split_whitespace();
+    let amount = parts.next().ok_or("no amount given")?;
+
+    let asset_id = match parts.next() {
+        Some(asset) => kp
+            .assets
+            .resolve(asset)
+            .ok_or_else(|| format!("unknown asset {asset}"))?,
+        None => *kp.wallet.provider().unwrap().base_asset_id(),
+    };
+
+    if parts.next().is_some() {
+        return Err(format!("expected `<amount> [asset]`, got {s}"));
+    }
+
+    Ok((asset_id, kp.assets.parse_amount(&asset_id, amount)?))
+}
@@ -0,0 +1,121 @@
+//! Predicate spends built by kpop but signed elsewhere.
+//!
+//! The owner spend path of the claimable predicate checks that witness 0 is the
+//! owner's signature of the transaction ID. Unsigned transactions carry a zeroed
+//! placeholder in that slot, which the signer replaces. Witnesses are not part of
+//! the transaction ID, so signing doesn't change it.
+//!
+//! # File format
+//!
+//! `kpop tx build` writes an [`UnsignedTransaction`] as a JSON object, which
+//! `kpop tx sign` fills in and `kpop tx submit` reads back:
+//!
+//! ```json
+//! {
+//!   "chain_id": 0,
+//!   "owner": "<hex address of the predicate owner>",
+//!   "tx_id": "<hex transaction ID the owner signs>",
+//!   "transaction": { <fuel-tx Script, serde encoded> }
+//! }
+//! ```
+//!
+//! The transaction holds the predicate coin inputs with their bytecode, so signing
+//! needs neither a node nor the kpop artifacts. `tx_id` is informational, the
+//! signer recomputes it from `transaction` and `chain_id` and refuses a mismatch.
+//! Witness 0 of `transaction` is 64 zero bytes until signed.
+
+use std::path::Path;
+
+use fuels::{
+    crypto::SecretKey,
+    prelude::*,
+    tx::{field::Witnesses, Script, TxId, UniqueIdentifier, Witness},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::UnsignedTxError;
+
+pub const OWNER_WITNESS_INDEX: usize = kpop_core::tx::SIGNER_WITNESS_INDEX as usize;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub chain_id: u64,
+    pub owner: Address,
+    pub tx_id: TxId,
+    pub transaction: Script,
+}
+
+impl UnsignedTransaction {
+    pub(crate) fn new(chain_id: ChainId, owner: Address, transaction: Script) -> Self {
+        Self {
+            chain_id: *chain_id,
+            owner,
+            tx_id: transaction.id(&chain_id),
+            transaction,
+        }
+    }
+
+    pub(crate) fn placeholder_witness() -> Witness {
+        kpop_core::tx::placeholder_witness()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, UnsignedTxError> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).map_err(|err| UnsignedTxError::Unreadable {
+                path: path.to_path_buf(),
+                reason: err.to_string(),
+            })?;
+        serde_json::from_str(&contents).map_err(|err| UnsignedTxError::Invalid {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        })
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), UnsignedTxError> {
+        let path = path.as_ref();
+        let contents =
+            serde_json::to_string_pretty(self).expect("should be able to encode transaction");
+        std::fs::write(path, contents).map_err(|err| UnsignedTxError::Unwritable {
+            path: path.to_path_buf(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Add the owner signature. Fails, leaving the transaction unsigned, if
+    /// `secret_key` doesn't belong to the owner or `tx_id` doesn't match the
+    /// transaction.
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<(), UnsignedTxError> {
+        let signer: Address = (*secret_key.public_key().hash()).into();
+        if signer != self.owner {
+            return Err(UnsignedTxError::WrongSigner {
+                owner: self.owner,
+                signer,
+            });
+        }
+
+        let actual = self.transaction.id(&ChainId::new(self.chain_id));
+        if actual != self.tx_id {
+            return Err(UnsignedTxError::IdMismatch {
+                tx_id: self.tx_id,
+                actual,
+            });
+        }
+
+        kpop_core::tx::sign(
+            &mut self.transaction,
+            secret_key,
+            ChainId::new(self.chain_id),
+        );
+
+        Ok(())
+    }
+
+    /// Whether the owner's witness is present and no longer the placeholder.
+    pub fn is_signed(&self) -> bool {
+        self.transaction
+            .witnesses()
+            .get(OWNER_WITNESS_INDEX)
+            .is_some_and(|witness| *witness != Self::placeholder_witness())
+    }
+}
@@ -0,0 +1,426 @@
+//! Stream of claim activity for an owner, built by following new blocks.
+//!
+//! Every new block is scanned for make-claim transactions against the owner, so
+//! a claim is reported even if it's resolved before the next poll. Claims are
+//! also read from every known contract and compared to the previous read. A claim
+//! that disappeared was either disproved or fulfilled; the `ClaimDisproved` and
+//! `ClaimFulfilled` events the contract logged in the blocks in between tell
+//! which. Only changes after the stream is created are reported.
+//!
+//! Node errors are reported as [`ClaimEvent::Error`] and the next poll retries
+//! from the last block that was read successfully.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Duration,
+};
+
+use fuels::{
+    core::codec::LogDecoder,
+    prelude::*,
+    tx::Receipt,
+    types::{transaction_response::TransactionType, tx_status::TxStatus},
+};
+use futures::{stream, Stream};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    claims::ClaimView,
+    claims_contract::{ClaimDisproved, ClaimFulfilled, ClaimsContract},
+    Claim, Kpop, CHALLENGE_PERIOD,
+};
+
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClaimEvent {
+    Filed {
+        block_height: u32,
+        claim: ClaimView,
+    },
+    Disproved {
+        block_height: u32,
+        claim: ClaimView,
+    },
+    Fulfilled {
+        block_height: u32,
+        claim: ClaimView,
+    },
+    PredicateBalance {
+        block_height: u32,
+        asset_id: String,
+        balance: u64,
+    },
+    /// The node couldn't be read. Polling continues.
+    Error {
+        message: String,
+    },
+}
+
+impl ClaimEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClaimEvent::Filed { .. } => "filed",
+            ClaimEvent::Disproved { .. } => "disproved",
+            ClaimEvent::Fulfilled { .. } => "fulfilled",
+            ClaimEvent::PredicateBalance { .. } => "predicate_balance",
+            ClaimEvent::Error { .. } => "error",
+        }
+    }
+
+    fn block_height(&self) -> Option<u32> {
+        match self {
+            ClaimEvent::Filed { block_height, .. }
+            | ClaimEvent::Disproved { block_height, .. }
+            | ClaimEvent::Fulfilled { block_height, .. }
+            | ClaimEvent::PredicateBalance { block_height, .. } => Some(*block_height),
+            ClaimEvent::Error { .. } => None,
+        }
+    }
+}
+
+type ClaimKey = (ContractId, u64);
+
+/// How a claim was resolved, with the block it happened in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    Disproved(u32),
+    Fulfilled(u32),
+}
+
+/// What happened in a range of blocks.
+#[derive(Debug, Default)]
+struct Activity {
+    filed: HashMap<ClaimKey, Claim>,
+    resolved: HashMap<ClaimKey, Resolution>,
+}
+
+struct Watcher {
+    kp: Kpop,
+    owner: Address,
+    /// The make-claim script of every known version and contract, configured
+    /// for the owner, mapped to the contract it files claims in.
+    scripts: HashMap<Vec<u8>, ContractId>,
+    /// Decoders of the events each known contract logs.
+    decoders: HashMap<ContractId, LogDecoder>,
+    poll_interval: Duration,
+    height: Option<u32>,
+    /// The last attempt failed, so the next one waits a poll interval first.
+    failed: bool,
+    claims: HashMap<ClaimKey, Claim>,
+    balance: HashMap<String, u64>,
+    pending: VecDeque<ClaimEvent>,
+}
+
+impl Kpop {
+    /// Follow claims against `owner` and the balance of their predicate.
+    pub fn watch(
+        &self,
+        owner: Address,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = ClaimEvent> + Send + 'static {
+        let decoders = self
+            .contract_ids()
+            .into_iter()
+            .map(|contract_id| {
+                let contract = ClaimsContract::new(contract_id, self.wallet.clone());
+                (contract_id, contract.log_decoder())
+            })
+            .collect();
+
+        let watcher = Watcher {
+            kp: self.clone(),
+            owner,
+            scripts: claim_scripts(self, owner),
+            decoders,
+            poll_interval,
+            height: None,
+            failed: false,
+            claims: HashMap::new(),
+            balance: HashMap::new(),
+            pending: VecDeque::new(),
+        };
+
+        stream::unfold(watcher, |mut watcher| async move {
+            let event = watcher.next_event().await;
+            Some((event, watcher))
+        })
+    }
+}
+
+impl Watcher {
+    async fn next_event(&mut self) -> ClaimEvent {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return event;
+            }
+
+            if self.height.is_some() || self.failed {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+
+            let result = match self.height {
+                None => self.snapshot().await,
+                Some(previous) => self.poll(previous).await,
+            };
+
+            self.failed = result.is_err();
+            if let Err(err) = result {
+                return ClaimEvent::Error {
+                    message: err.to_string(),
+                };
+            }
+        }
+    }
+
+    async fn snapshot(&mut self) -> Result<()> {
+        let height = self.latest_height().await?;
+        let claims = self.read_claims().await?;
+        let balance = self.read_balance().await?;
+
+        self.height = Some(height);
+        self.claims = claims;
+        self.balance = balance;
+
+        Ok(())
+    }
+
+    /// Read everything since block `previous`. State is only updated once all
+    /// reads succeeded, so a failed poll can simply be retried.
+    async fn poll(&mut self, previous: u32) -> Result<()> {
+        let latest = self.latest_height().await?;
+
+        if latest == previous {
+            return Ok(());
+        }
+
+        let mut activity = self.scan(previous + 1, latest).await?;
+        let claims = self.read_claims().await?;
+        let balance = self.read_balance().await?;
+
+        // The previous read may already have seen claims filed after `previous`.
+        activity
+            .filed
+            .retain(|key, _| !self.claims.contains_key(key));
+        for (key, claim) in &claims {
+            if !self.claims.contains_key(key) {
+                activity.filed.entry(*key).or_insert_with(|| claim.clone());
+            }
+        }
+
+        let mut events = Vec::new();
+
+        for (key, claim) in &activity.filed {
+            events.push(ClaimEvent::Filed {
+                block_height: claim.block_height,
+                claim: ClaimView::new(key.0, claim.clone()),
+            });
+        }
+
+        for (key, claim) in self.claims.iter().chain(&activity.filed) {
+            if !claims.contains_key(key) {
+                let resolution = activity.resolved.get(key).copied();
+                events.push(resolution_event(key.0, claim, resolution, latest));
+            }
+        }
+
+        // Filed events go first within a block, the sort is stable.
+        events.sort_by_key(|event| event.block_height());
+
+        let asset_ids: HashSet<&String> = balance.keys().chain(self.balance.keys()).collect();
+
+        for asset_id in asset_ids {
+            let old = self.balance.get(asset_id).copied().unwrap_or(0);
+            let new = balance.get(asset_id).copied().unwrap_or(0);
+
+            if old != new {
+                events.push(ClaimEvent::PredicateBalance {
+                    block_height: latest,
+                    asset_id: asset_id.to_string(),
+                    balance: new,
+                });
+            }
+        }
+
+        self.pending.extend(events);
+        self.height = Some(latest);
+        self.claims = claims;
+        self.balance = balance;
+
+        Ok(())
+    }
+
+    async fn latest_height(&self) -> Result<u32> {
+        self.kp
+            .wallet
+            .provider()
+            .unwrap()
+            .latest_block_height()
+            .await
+    }
+
+    async fn read_claims(&self) -> Result<HashMap<ClaimKey, Claim>> {
+        let mut claims = HashMap::new();
+
+        for contract_id in self.kp.contract_ids() {
+            for claim in self.kp.try_get_claims_of(contract_id, self.owner).await? {
+                claims.insert((contract_id, claim.id), claim);
+            }
+        }
+
+        Ok(claims)
+    }
+
+    async fn read_balance(&self) -> Result<HashMap<String, u64>> {
+        self.kp
+            .wallet
+            .provider()
+            .unwrap()
+            .get_balances(self.kp.predicate(self.owner).await.address())
+            .await
+    }
+
+    /// Claims filed against the owner and claims resolved in known claims
+    /// contracts in blocks `from..=to`.
+    async fn scan(&self, from: u32, to: u32) -> Result<Activity> {
+        let provider = self.kp.wallet.provider().unwrap();
+        let mut activity = Activity::default();
+
+        for height in from..=to {
+            let Some(block) = provider.block_by_height(height.into()).await? else {
+                continue;
+            };
+
+            for tx_id in block.transactions {
+                let Some(response) = provider.get_transaction_by_id(&tx_id).await? else {
+                    continue;
+                };
+                let TxStatus::Success { receipts, .. } = response.status else {
+                    continue;
+                };
+
+                for (contract_id, decoder) in &self.decoders {
+                    for event in decoder.decode_logs_with_type::<ClaimDisproved>(&receipts)? {
+                        activity.resolved.insert(
+                            (*contract_id, event.claim_id),
+                            Resolution::Disproved(height),
+                        );
+                    }
+                    for event in decoder.decode_logs_with_type::<ClaimFulfilled>(&receipts)? {
+                        activity.resolved.insert(
+                            (*contract_id, event.claim_id),
+                            Resolution::Fulfilled(height),
+                        );
+                    }
+                }
+
+                let TransactionType::Script(tx) = response.transaction else {
+                    continue;
+                };
+                if let Some(contract_id) = self.scripts.get(tx.script()) {
+                    if let Some(claim) = filed_claim(
+                        *contract_id,
+                        self.owner,
+                        tx.script_data(),
+                        &receipts,
+                        height,
+                    ) {
+                        activity.filed.insert((*contract_id, claim.id), claim);
+                    }
+                }
+            }
+        }
+
+        Ok(activity)
+    }
+}
+
+/// The make-claim script of every version and contract `kp` knows, configured
+/// for `owner`, mapped to the contract it files claims in.
+fn claim_scripts(kp: &Kpop, owner: Address) -> HashMap<Vec<u8>, ContractId> {
+    let mut scripts = HashMap::new();
+
+    for version in kp.artifacts.versions() {
+        let script = version.script().expect("should be able to read the script");
+        for contract_id in kp.contract_ids() {
+            let code = kpop_core::address::script_bytecode(script.program(), contract_id, owner)
+                .expect("version's ABI should list the script configurables");
+            scripts.insert(code, contract_id);
+        }
+    }
+
+    scripts
+}
+
+/// The claim filed by a successful make-claim transaction.
+///
+/// The recipient is the script's first argument. The contract call receipt has
+/// the amount and asset, and the contract's return data the claim ID.
+fn filed_claim(
+    contract_id: ContractId,
+    owner: Address,
+    script_data: &[u8],
+    receipts: &[Receipt],
+    block_height: u32,
+) -> Option<Claim> {
+    let recipient: [u8; 32] = script_data.get(..32)?.try_into().ok()?;
+
+    let (amount, asset) = receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Call {
+            to,
+            amount,
+            asset_id,
+            ..
+        } if *to == contract_id => Some((*amount, *asset_id)),
+        _ => None,
+    })?;
+
+    let id = receipts.iter().find_map(|receipt| match receipt {
+        Receipt::ReturnData { id, data, .. } if *id == contract_id => {
+            let bytes: [u8; 8] = data.as_deref()?.try_into().ok()?;
+            Some(u64::from_be_bytes(bytes))
+        }
+        _ => None,
+    })?;
+
+    Some(Claim {
+        id,
+        owner,
+        recipient: recipient.into(),
+        asset,
+        amount,
+        block_height,
+    })
+}
+
+/// The event for a claim that's gone, going by the `resolution` the contract
+/// logged.
+fn resolution_event(
+    contract_id: ContractId,
+    claim: &Claim,
+    resolution: Option<Resolution>,
+    latest: u32,
+) -> ClaimEvent {
+    let resolution = resolution.unwrap_or_else(|| {
+        // Contracts deployed before the events were added log nothing, but a
+        // claim can't be fulfilled before the challenge period is over.
+        if latest >= claim.block_height + CHALLENGE_PERIOD {
+            Resolution::Fulfilled(latest)
+        } else {
+            Resolution::Disproved(latest)
+        }
+    });
+
+    let claim = ClaimView::new(contract_id, claim.clone());
+    match resolution {
+        Resolution::Disproved(block_height) => ClaimEvent::Disproved {
+            block_height,
+            claim,
+        },
+        Resolution::Fulfilled(block_height) => ClaimEvent::Fulfilled {
+            block_height,
+            claim,
+        },
+    }
+}
@@ -0,0 +1,161 @@
+use fuels::{prelude::*, types::Identity};
+use kpop::address::{self, AddressBook};
+use test_support::{temp_path, Harness};
+
+fn recipient_address() -> Address {
+    (*test_support::private_key(1).public_key().hash()).into()
+}
+
+fn no_contacts() -> AddressBook {
+    AddressBook::load(temp_path("no-contacts"))
+}
+
+#[test]
+fn bech32_and_hex_addresses_are_the_same_identity() {
+    let address = recipient_address();
+    let bech32 = Bech32Address::from(address).to_string();
+    let book = no_contacts();
+
+    let expected = Identity::Address(address);
+    assert_eq!(address::parse_identity(&bech32, &book), Ok(expected));
+    assert_eq!(
+        address::parse_identity(&format!("0x{address}"), &book),
+        Ok(expected)
+    );
+    assert_eq!(
+        address::parse_identity(&address.to_string(), &book),
+        Ok(expected)
+    );
+    assert!(address::parse_identity("fuel1nope", &book).is_err());
+    assert!(address::parse_identity("0x1234", &book).is_err());
+}
+
+#[test]
+fn contracts_are_only_accepted_as_identities() {
+    let contract_id = ContractId::from([7; 32]);
+    let s = format!("contract:0x{contract_id}");
+    let book = no_contacts();
+
+    assert_eq!(
+        address::parse_identity(&s, &book),
+        Ok(Identity::ContractId(contract_id))
+    );
+    assert_eq!(
+        address::parse_address(&s, &book),
+        Err(format!("{s} is a contract, expected an address"))
+    );
+}
+
+#[test]
+fn identities_render_in_a_form_that_parses_back() {
+    let book = no_contacts();
+
+    for identity in [
+        Identity::Address(recipient_address()),
+        Identity::ContractId(ContractId::from([7; 32])),
+    ] {
+        let rendered = address::display_identity(&identity);
+        assert_eq!(address::parse_identity(&rendered, &book), Ok(identity));
+    }
+
+    assert!(address::display_address(&recipient_address()).starts_with("fuel1"));
+}
+
+#[test]
+fn names_resolve_from_the_address_book() {
+    let path = temp_path("contacts");
+    let contract_id = ContractId::from([7; 32]);
+    let alice = Bech32Address::from(recipient_address()).to_string();
+    std::fs::write(
+        &path,
+        serde_json::json!({
+            "contacts": [
+                { "name": "alice", "address": alice },
+                { "name": "vault", "address": format!("contract:{contract_id}") },
+            ]
+        })
+        .to_string(),
+    )
+    .unwrap();
+
+    let book = AddressBook::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        address::parse_address("Alice", &book),
+        Ok(recipient_address())
+    );
+    assert_eq!(
+        address::parse_identity("vault", &book),
+        Ok(Identity::ContractId(contract_id))
+    );
+    assert!(address::parse_identity("bob", &book).is_err());
+}
+
+#[test]
+fn contacts_are_saved_and_shown_with_their_name() {
+    let path = temp_path("saved-contacts");
+    let alice = Identity::Address(recipient_address());
+    let vault = Identity::ContractId(ContractId::from([7; 32]));
+
+    let mut book = AddressBook::load(&path);
+    book.add("alice", vault).unwrap();
+    book.add("Alice", alice).unwrap();
+    book.add("vault", vault).unwrap();
+    book.add("bob", alice).unwrap();
+    assert!(book.remove("BOB"));
+    assert!(!book.remove("bob"));
+    book.save();
+
+    let loaded = AddressBook::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        loaded.contacts(),
+        [("Alice".to_string(), alice), ("vault".to_string(), vault)]
+    );
+    assert_eq!(
+        loaded.display_address(&recipient_address()),
+        format!("Alice ({})", address::display_address(&recipient_address()))
+    );
+    assert_eq!(
+        loaded.display_address(&Address::zeroed()),
+        address::display_address(&Address::zeroed())
+    );
+}
+
+#[test]
+fn names_that_look_like_addresses_are_rejected() {
+    let mut book = no_contacts();
+    let alice = Identity::Address(recipient_address());
+
+    assert!(book.add("", alice).is_err());
+    assert!(book.add(&address::display_identity(&alice), alice).is_err());
+    assert!(book
+        .add(&format!("contract:{}", ContractId::zeroed()), alice)
+        .is_err());
+}
+
+#[tokio::test]
+async fn predicate_funds_can_be_sent_to_a_contract() {
+    let harness = Harness::builder().build().await;
+    let provider = harness.provider.clone();
+
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+    kp.fund_predicate(None, 10_000).await.unwrap();
+
+    let outcome = kp
+        .send_to(&Identity::ContractId(kp.contract_id), None, 1_000)
+        .await
+        .unwrap();
+
+    assert!(outcome.revert_reason.is_none());
+    assert_eq!(
+        provider
+            .get_contract_asset_balance(&kp.contract_id.into(), AssetId::zeroed())
+            .await
+            .unwrap(),
+        1_000
+    );
+    assert!(kp.predicate_balance().await[&AssetId::zeroed().to_string()] <= 9_000);
+}
@@ -0,0 +1,154 @@
+use std::path::{Path, PathBuf};
+
+use fuels::prelude::*;
+use kpop::{
+    artifacts::{self, ArtifactVersion, Artifacts},
+    error::ArtifactError,
+};
+use test_support::{temp_dir, Harness};
+
+/// The local debug builds with padding after the script, which moves the
+/// predicate to a different address without touching any configurable offset.
+fn older_build(dir: &Path) -> ArtifactVersion {
+    let dev = artifacts::dev_version();
+    let copy = |from: &PathBuf| {
+        let to = dir.join(from.file_name().unwrap());
+        std::fs::copy(from, &to).unwrap();
+        to
+    };
+
+    let version = ArtifactVersion {
+        name: "older".to_string(),
+        predicate_path: copy(&dev.predicate_path),
+        predicate_abi_path: copy(&dev.predicate_abi_path),
+        script_path: copy(&dev.script_path),
+        script_abi_path: copy(&dev.script_abi_path),
+    };
+
+    let mut script = std::fs::read(&version.script_path).unwrap();
+    script.extend([0; 8]);
+    std::fs::write(&version.script_path, script).unwrap();
+
+    version
+}
+
+/// Artifacts with the older build pinned as `v0` and the current one as `v1`.
+fn two_versions(name: &str) -> (Artifacts, Artifacts) {
+    let dir = temp_dir(name);
+    let source = temp_dir(&format!("{name}-source"));
+
+    let mut artifacts = Artifacts::load(&dir).unwrap();
+    artifacts.pin_from("v0", &older_build(&source)).unwrap();
+    let only_v0 = artifacts.clone();
+    artifacts.pin("v1").unwrap();
+
+    (only_v0, artifacts)
+}
+
+fn owner(harness: &Harness) -> kpop::Kpop {
+    harness.load(harness.owner_key(), kpop::Kpop::load)
+}
+
+fn base(balances: &std::collections::HashMap<String, u64>) -> u64 {
+    balances
+        .get(&AssetId::zeroed().to_string())
+        .copied()
+        .unwrap_or(0)
+}
+
+#[test]
+fn nothing_pinned_means_the_dev_build() {
+    let artifacts = Artifacts::load(temp_dir("artifacts-empty")).unwrap();
+
+    assert_eq!(artifacts.current(), artifacts::dev_version());
+    assert_eq!(artifacts.versions(), [artifacts::dev_version()]);
+    assert!(artifacts.previous().is_empty());
+}
+
+#[test]
+fn pinned_versions_survive_a_reload() {
+    let dir = temp_dir("artifacts-reload");
+    let mut artifacts = Artifacts::load(&dir).unwrap();
+    let pinned = artifacts.pin("v1").unwrap();
+
+    let reloaded = Artifacts::load(&dir).unwrap();
+    assert_eq!(reloaded.current(), pinned);
+    assert_eq!(reloaded.versions(), [pinned]);
+}
+
+#[test]
+fn pinning_a_version_twice_is_an_error() {
+    let mut artifacts = Artifacts::load(temp_dir("artifacts-twice")).unwrap();
+    artifacts.pin("v1").unwrap();
+
+    assert_eq!(
+        artifacts.pin("v1").unwrap_err(),
+        ArtifactError::AlreadyPinned("v1".to_string())
+    );
+    assert_eq!(artifacts.versions().len(), 1);
+}
+
+#[test]
+fn changed_files_fail_to_load() {
+    let dir = temp_dir("artifacts-tampered");
+    let pinned = Artifacts::load(&dir).unwrap().pin("v1").unwrap();
+
+    let mut abi = std::fs::read_to_string(&pinned.script_abi_path).unwrap();
+    abi.push('\n');
+    std::fs::write(&pinned.script_abi_path, abi).unwrap();
+
+    assert_eq!(
+        Artifacts::load(&dir).unwrap_err(),
+        ArtifactError::HashMismatch {
+            version: "v1".to_string(),
+            path: pinned.script_abi_path,
+        }
+    );
+}
+
+#[tokio::test]
+async fn every_version_has_its_own_predicate_address() {
+    let harness = Harness::builder().wallets(1).build().await;
+    let (_, artifacts) = two_versions("artifacts-addresses");
+    let kp = owner(&harness).with_artifacts(artifacts);
+
+    let addresses = kp.predicate_addresses().await;
+
+    let names = addresses
+        .iter()
+        .map(|(version, _)| version.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["v0", "v1"]);
+    assert_ne!(addresses[0].1, addresses[1].1);
+    assert_eq!(addresses[1].1, kp.predicate_address().await);
+
+    // v1 is a copy of the dev build, so it's where a fresh kpop sends funds too.
+    assert_eq!(addresses[1].1, owner(&harness).predicate_address().await);
+}
+
+#[tokio::test]
+async fn funds_at_previous_versions_are_stranded_until_migrated() {
+    let harness = Harness::builder().wallets(1).build().await;
+    let (only_v0, artifacts) = two_versions("artifacts-migrate");
+
+    owner(&harness)
+        .with_artifacts(only_v0)
+        .fund_predicate(None, 10_000)
+        .await
+        .unwrap();
+    let kp = owner(&harness).with_artifacts(artifacts);
+
+    let stranded = kp.stranded_balances().await;
+    assert_eq!(stranded.len(), 1);
+    assert_eq!(stranded[0].0.name, "v0");
+    assert_eq!(base(&stranded[0].1), 10_000);
+    assert_eq!(base(&kp.predicate_balance().await), 0);
+
+    let outcomes = kp.migrate().await.unwrap();
+
+    assert_eq!(outcomes.len(), 1);
+    assert!(outcomes[0].revert_reason.is_none());
+    assert!(kp.stranded_balances().await.is_empty());
+    assert_eq!(base(&kp.predicate_balance().await), 10_000);
+    assert!(kp.migrate().await.unwrap().is_empty());
+}
@@ -0,0 +1,116 @@
+use std::str::FromStr;
+
+use fuels::types::AssetId;
+use kpop::assets::{self, AssetInfo, AssetRegistry};
+use test_support::temp_path;
+
+const ETH: &str = "0xf8f8b6283d7fa5b672b530cbb84fcccb4ff8dc40f8176ef4544ddb1f1952ad07";
+
+fn eth() -> AssetInfo {
+    AssetInfo {
+        symbol: "ETH".to_string(),
+        asset_id: AssetId::from_str(ETH).unwrap(),
+        decimals: 9,
+    }
+}
+
+#[test]
+fn amounts_are_parsed_in_whole_units() {
+    assert_eq!(assets::parse_amount("1.5", 9), Ok(1_500_000_000));
+    assert_eq!(assets::parse_amount("2", 9), Ok(2_000_000_000));
+    assert_eq!(assets::parse_amount("0.000000001", 9), Ok(1));
+    assert_eq!(assets::parse_amount(".5", 1), Ok(5));
+    assert_eq!(assets::parse_amount("1000", 0), Ok(1000));
+
+    assert!(assets::parse_amount("0.0000000001", 9).is_err());
+    assert!(assets::parse_amount("1.5", 0).is_err());
+    assert!(assets::parse_amount("", 9).is_err());
+    assert!(assets::parse_amount("1,5", 9).is_err());
+    assert!(assets::parse_amount("-1", 9).is_err());
+    assert!(assets::parse_amount("18446744073709551616", 0).is_err());
+}
+
+#[test]
+fn amounts_are_formatted_without_trailing_zeros() {
+    assert_eq!(assets::format_amount(1_500_000_000, 9), "1.5");
+    assert_eq!(assets::format_amount(2_000_000_000, 9), "2");
+    assert_eq!(assets::format_amount(1, 9), "0.000000001");
+    assert_eq!(assets::format_amount(0, 9), "0");
+    assert_eq!(assets::format_amount(1000, 0), "1000");
+}
+
+#[test]
+fn balances_are_sorted_by_asset() {
+    let balance = [(ETH.to_string(), 5), (AssetId::zeroed().to_string(), 7)]
+        .into_iter()
+        .collect();
+
+    assert_eq!(
+        assets::sorted_balance(balance),
+        [(AssetId::zeroed(), 7), (eth().asset_id, 5)]
+    );
+}
+
+#[test]
+fn symbols_and_ids_resolve() {
+    let mut registry = AssetRegistry::load(temp_path("unused-assets"));
+    registry.add(eth());
+
+    let eth_id = AssetId::from_str(ETH).unwrap();
+    assert_eq!(registry.resolve("ETH"), Some(eth_id));
+    assert_eq!(registry.resolve("eth"), Some(eth_id));
+    assert_eq!(registry.resolve(ETH), Some(eth_id));
+    assert_eq!(
+        registry.resolve(&AssetId::zeroed().to_string()),
+        Some(AssetId::zeroed())
+    );
+    assert_eq!(registry.resolve("USDC"), None);
+
+    assert_eq!(registry.parse_amount(&eth_id, "1.5"), Ok(1_500_000_000));
+    assert_eq!(registry.parse_amount(&AssetId::zeroed(), "1500"), Ok(1500));
+}
+
+#[test]
+fn amounts_are_displayed_with_symbol_and_raw_value() {
+    let mut registry = AssetRegistry::load(temp_path("unused-assets"));
+    registry.add(eth());
+
+    assert_eq!(
+        registry.display(&eth().asset_id, 1_500_000_000),
+        "1.5 ETH (1500000000)"
+    );
+    assert_eq!(
+        registry.display(&AssetId::zeroed(), 1500),
+        format!("1500 of {}", AssetId::zeroed())
+    );
+}
+
+#[test]
+fn registry_round_trips_through_its_file() {
+    let path = temp_path("assets");
+    let mut registry = AssetRegistry::load(&path);
+    registry.add(eth());
+    registry.add(AssetInfo {
+        symbol: "USDC".to_string(),
+        asset_id: AssetId::zeroed(),
+        decimals: 6,
+    });
+    registry.add(AssetInfo {
+        decimals: 18,
+        ..eth()
+    });
+    assert!(registry.remove("usdc"));
+    assert!(!registry.remove("usdc"));
+    registry.save();
+
+    let loaded = AssetRegistry::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        loaded.assets(),
+        [AssetInfo {
+            decimals: 18,
+            ..eth()
+        }]
+    );
+}
@@ -0,0 +1,226 @@
+use std::time::Duration;
+
+use fuels::prelude::*;
+use kpop::{
+    address::AddressBook,
+    batch::{self, BatchOptions, Operation, Row, RowResult},
+};
+use test_support::{temp_path, Harness};
+
+async fn setup() -> (kpop::Kpop, WalletUnlocked) {
+    let harness = Harness::builder().wallets(2).build().await;
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+
+    (kp, harness.claimant().clone())
+}
+
+fn no_contacts() -> AddressBook {
+    AddressBook::load(temp_path("batch-no-contacts"))
+}
+
+fn no_retries() -> BatchOptions {
+    BatchOptions {
+        concurrency: 1,
+        retries: 0,
+        retry_delay: Duration::ZERO,
+    }
+}
+
+#[test]
+fn csv_and_json_rows_are_equivalent() {
+    let csv = "op,recipient,owner,asset_id,amount,claim_id,contract_id\n\
+               fund,,,,10000,,\n\
+               disprove,,,,,3,\n";
+    let json = r#"[
+        { "op": "fund", "amount": 10000 },
+        { "op": "disprove", "claim_id": 3 }
+    ]"#;
+
+    let rows = batch::parse_csv(csv).unwrap();
+
+    assert_eq!(rows, batch::parse_json(json).unwrap());
+    assert_eq!(
+        batch::validate(&rows, &no_contacts()).unwrap(),
+        vec![
+            Operation::Fund {
+                asset_id: None,
+                amount: 10_000
+            },
+            Operation::Disprove {
+                contract_id: None,
+                claim_id: 3
+            },
+        ]
+    );
+}
+
+#[test]
+fn every_invalid_row_is_reported() {
+    let rows = batch::parse_json(
+        r#"[
+            { "op": "fund", "amount": 10000 },
+            { "op": "send-to", "recipient": "nope", "amount": 1 },
+            { "op": "fulfill" },
+            { "op": "burn", "amount": 1 },
+            { "op": "fund", "asset_id": "xyz", "amount": 1 }
+        ]"#,
+    )
+    .unwrap();
+
+    let errors = batch::validate(&rows, &no_contacts()).unwrap_err();
+
+    assert_eq!(
+        errors,
+        vec![
+            (
+                2,
+                "recipient: nope is not an address, contract or known name".to_string()
+            ),
+            (3, "fulfill needs a claim_id".to_string()),
+            (4, "unknown op burn".to_string()),
+            (5, "invalid asset_id xyz".to_string()),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn batch_reports_each_row() -> Result<()> {
+    let (kp, recipient) = setup().await;
+    let recipient_before = recipient.get_asset_balance(&AssetId::zeroed()).await?;
+
+    let rows = vec![
+        Row {
+            op: "fund".to_string(),
+            amount: Some(10_000),
+            ..Default::default()
+        },
+        Row {
+            op: "disprove".to_string(),
+            claim_id: Some(42),
+            ..Default::default()
+        },
+        Row {
+            op: "send-to".to_string(),
+            recipient: Some(recipient.address().to_string()),
+            amount: Some(100),
+            ..Default::default()
+        },
+    ];
+    let operations = batch::validate(&rows, &no_contacts()).unwrap();
+
+    let results = batch::execute(&kp, operations, no_retries()).await;
+
+    assert_eq!(
+        results.iter().map(|result| result.row).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert!(results[0].is_ok() && results[0].tx_id.is_some());
+    // The disprove reverted on chain, so its transaction is reported.
+    assert!(!results[1].is_ok() && results[1].tx_id.is_some());
+    assert!(results[2].is_ok() && results[2].tx_id.is_some());
+    assert_eq!(
+        recipient.get_asset_balance(&AssetId::zeroed()).await?,
+        recipient_before + 100
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rows_failing_before_submission_are_retried() {
+    let (kp, _) = setup().await;
+
+    // More than the wallet holds, so no transaction can be built.
+    let operations = vec![Operation::Fund {
+        asset_id: None,
+        amount: u64::MAX,
+    }];
+    let options = BatchOptions {
+        retries: 2,
+        ..no_retries()
+    };
+
+    let results = batch::execute(&kp, operations, options).await;
+
+    assert_eq!(results[0].attempts, 3);
+    assert!(results[0]
+        .error
+        .as_ref()
+        .is_some_and(|err| err.contains("not submitted")));
+    assert!(results[0].tx_id.is_none());
+}
+
+#[tokio::test]
+async fn reverted_rows_are_not_retried() {
+    let (kp, _) = setup().await;
+
+    let operations = vec![Operation::Fulfill {
+        contract_id: None,
+        claim_id: 42,
+    }];
+    let options = BatchOptions {
+        retries: 2,
+        ..no_retries()
+    };
+
+    let results = batch::execute(&kp, operations, options).await;
+
+    assert_eq!(results[0].attempts, 1);
+    assert!(results[0].tx_id.is_some());
+    assert!(results[0]
+        .error
+        .as_ref()
+        .is_some_and(|err| err.contains("reverted")));
+}
+
+#[tokio::test]
+async fn concurrent_rows_all_complete() {
+    let (kp, _) = setup().await;
+
+    let operations = (0..4)
+        .map(|_| Operation::Fund {
+            asset_id: None,
+            amount: 1_000,
+        })
+        .collect();
+    let options = BatchOptions {
+        concurrency: 4,
+        retries: 5,
+        retry_delay: Duration::from_millis(100),
+    };
+
+    let results = batch::execute(&kp, operations, options).await;
+
+    assert!(results.iter().all(RowResult::is_ok));
+    assert_eq!(
+        kp.predicate_balance().await[&AssetId::zeroed().to_string()],
+        4_000
+    );
+}
+
+#[test]
+fn reports_are_written_as_csv_or_json() {
+    let results = vec![RowResult {
+        row: 1,
+        op: "fund".to_string(),
+        attempts: 1,
+        tx_id: Some("00".repeat(32)),
+        claim_id: None,
+        error: None,
+    }];
+    let dir = std::env::temp_dir();
+    let csv_path = dir.join(format!("kpop-report-{}.csv", std::process::id()));
+    let json_path = dir.join(format!("kpop-report-{}.json", std::process::id()));
+
+    batch::write_report(&csv_path, &results);
+    batch::write_report(&json_path, &results);
+
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let json: Vec<RowResult> =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    std::fs::remove_file(&csv_path).unwrap();
+    std::fs::remove_file(&json_path).unwrap();
+
+    assert!(csv.starts_with("row,op,attempts,tx_id,claim_id,error\n"));
+    assert_eq!(json, results);
+}
@@ -0,0 +1,76 @@
+use std::process::Command;
+
+fn kpop(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_kpop"))
+        .args(args)
+        .output()
+        .expect("should be able to run kpop");
+    assert!(output.status.success(), "kpop {args:?} failed: {output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Names and descriptions listed under `Commands:` in `kpop <args> --help`.
+fn subcommands(args: &[&str]) -> Vec<(String, String)> {
+    let help = kpop(&[args, &["--help"]].concat());
+
+    help.lines()
+        .skip_while(|line| *line != "Commands:")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        // Wrapped descriptions continue on lines indented further.
+        .filter(|line| !line.starts_with("   "))
+        .map(|line| {
+            let line = line.trim();
+            let (name, about) = line.split_once(' ').unwrap_or((line, ""));
+            (name.to_string(), about.trim().to_string())
+        })
+        .filter(|(name, _)| name != "help")
+        .collect()
+}
+
+#[test]
+fn every_subcommand_has_a_description() {
+    let top = subcommands(&[]);
+    assert!(top.iter().any(|(name, _)| name == "send-to"));
+
+    for (name, about) in &top {
+        assert!(!about.is_empty(), "kpop {name} has no description");
+
+        for (sub, about) in subcommands(&[name]) {
+            assert!(!about.is_empty(), "kpop {name} {sub} has no description");
+        }
+    }
+}
+
+#[test]
+fn completions_are_generated_for_common_shells() {
+    for shell in ["bash", "zsh", "fish"] {
+        let script = kpop(&["completions", shell]);
+
+        assert!(
+            script.contains("send-to"),
+            "{shell} completions lack send-to"
+        );
+    }
+}
+
+#[test]
+fn man_pages_are_written_for_every_subcommand() {
+    let dir = test_support::temp_dir("man");
+
+    kpop(&["man", "--out-dir", dir.to_str().unwrap()]);
+
+    for page in [
+        "kpop.1",
+        "kpop-fund.1",
+        "kpop-tx-sign.1",
+        "kpop-contacts-add.1",
+    ] {
+        assert!(dir.join(page).exists(), "{page} is missing");
+    }
+    let fund = std::fs::read_to_string(dir.join("kpop-fund.1")).unwrap();
+    assert!(fund.contains("Move funds from the wallet into its predicate"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,25 @@
+use fuels::prelude::*;
+use kpop::artifacts;
+use test_support::Harness;
+
+#[tokio::test]
+async fn core_derives_the_predicate_address_kpop_uses() {
+    let harness = Harness::builder().wallets(1).build().await;
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+
+    let dev = artifacts::dev_version();
+    let address = kpop_core::address::predicate_address(
+        dev.predicate().unwrap().program(),
+        dev.script().unwrap().program(),
+        kp.contract_id,
+        kp.wallet.address().into(),
+    )
+    .unwrap();
+
+    assert_eq!(Bech32Address::from(address), kp.predicate_address().await);
+    // Configured through the abigen bindings instead of the ABI offsets.
+    assert_eq!(
+        Bech32Address::from(address),
+        *harness.predicate().await.address()
+    );
+}
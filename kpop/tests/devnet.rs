@@ -0,0 +1,119 @@
+#![cfg(feature = "devnet")]
+
+use std::str::FromStr;
+
+use fuels::{crypto::SecretKey, prelude::*};
+use kpop::{
+    devnet::{Devnet, AMOUNT_PER_COIN, COINS_PER_WALLET, PREFUNDED_KEYS},
+    node,
+    profile::Profile,
+    CHALLENGE_PERIOD,
+};
+use test_support::temp_path;
+
+async fn launch() -> Devnet {
+    Devnet::launch("127.0.0.1:0".parse().unwrap()).await
+}
+
+#[tokio::test]
+async fn wallets_are_funded_and_the_contract_is_deployed() {
+    let devnet = launch().await;
+
+    assert_eq!(devnet.wallets.len(), PREFUNDED_KEYS.len());
+    for wallet in &devnet.wallets[1..] {
+        assert_eq!(
+            wallet.get_asset_balance(&AssetId::zeroed()).await.unwrap(),
+            COINS_PER_WALLET * AMOUNT_PER_COIN
+        );
+    }
+
+    assert!(devnet.kpop.get_claims().await.is_empty());
+    assert!(devnet
+        .provider
+        .contract_exists(&devnet.kpop.contract_id.into())
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn profile_connects_to_the_devnet() {
+    let devnet = launch().await;
+    let path = temp_path("devnet-profile");
+
+    devnet.profile().save(&path);
+    let profile = Profile::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let provider = Provider::connect(profile.provider_url.unwrap())
+        .await
+        .unwrap();
+    let pk = SecretKey::from_str(&profile.private_key.unwrap()).unwrap();
+    let contract_id = ContractId::from_str(&profile.contract_id.unwrap()).unwrap();
+    let kp = kpop::Kpop::load(provider, pk, contract_id);
+
+    assert_eq!(kp.wallet.address(), devnet.wallets[0].address());
+    assert_eq!(kp.contract_id, devnet.kpop.contract_id);
+}
+
+#[tokio::test]
+async fn advancing_skips_the_challenge_period() {
+    let devnet = launch().await;
+    let claimant = kpop::Kpop::load(
+        devnet.provider.clone(),
+        SecretKey::from_str(PREFUNDED_KEYS[1]).unwrap(),
+        devnet.kpop.contract_id,
+    );
+    devnet.kpop.fund_predicate(None, 10_000).await.unwrap();
+    let owner: Address = devnet.kpop.wallet.address().into();
+    let claim_id = claimant.claim(owner, None, 1_000).await.unwrap().claim_ids[0];
+
+    let before = devnet.provider.latest_block_height().await.unwrap();
+    let height = node::advance(&devnet.provider, CHALLENGE_PERIOD).await;
+    assert_eq!(height, before + CHALLENGE_PERIOD);
+
+    let outcome = claimant.fulfill_claim(claim_id).await.unwrap();
+    assert!(outcome.revert_reason.is_none());
+}
+
+#[tokio::test]
+async fn blocks_are_produced_until_a_claim_is_fulfillable() {
+    let devnet = launch().await;
+    let claimant = kpop::Kpop::load(
+        devnet.provider.clone(),
+        SecretKey::from_str(PREFUNDED_KEYS[1]).unwrap(),
+        devnet.kpop.contract_id,
+    );
+    devnet.kpop.fund_predicate(None, 10_000).await.unwrap();
+    let owner: Address = devnet.kpop.wallet.address().into();
+    claimant.claim(owner, None, 1_000).await.unwrap();
+    let claim = devnet.kpop.get_claims().await.remove(0);
+
+    let height = node::advance_until_fulfillable(&devnet.provider, &claim).await;
+    assert_eq!(height, claim.block_height + CHALLENGE_PERIOD - 1);
+
+    // Already fulfillable, nothing more to produce.
+    assert_eq!(
+        node::advance_until_fulfillable(&devnet.provider, &claim).await,
+        height
+    );
+
+    let outcome = claimant.fulfill_claim(claim.id).await.unwrap();
+    assert!(outcome.revert_reason.is_none());
+}
+
+#[test]
+fn partial_profiles_leave_other_settings_unset() {
+    let path = temp_path("partial-profile");
+    std::fs::write(&path, r#"{ "provider_url": "127.0.0.1:4000" }"#).unwrap();
+
+    let profile = Profile::load(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        profile,
+        Profile {
+            provider_url: Some("127.0.0.1:4000".to_string()),
+            ..Default::default()
+        }
+    );
+}
@@ -0,0 +1,87 @@
+use fuels::prelude::*;
+use kpop::error::{DisproveManyError, RejectedClaims};
+use test_support::Harness;
+
+/// Owner with a funded predicate and three claims against it.
+async fn setup() -> (kpop::Kpop, Vec<u64>) {
+    let harness = Harness::builder().wallets(2).build().await;
+
+    let (owner, claimant) = harness.parties(kpop::Kpop::load);
+    owner.fund_predicate(None, 10_000).await.unwrap();
+
+    let mut claim_ids = Vec::new();
+    for _ in 0..3 {
+        let outcome = claimant
+            .claim(owner.wallet.address().into(), None, 1_000)
+            .await
+            .unwrap();
+        claim_ids.push(outcome.claim_ids[0]);
+    }
+
+    (owner, claim_ids)
+}
+
+#[tokio::test]
+async fn disproves_several_claims_in_one_transaction() {
+    let (owner, claim_ids) = setup().await;
+
+    let outcome = owner
+        .disprove_many(&claim_ids)
+        .await
+        .expect("should be able to disprove all claims");
+
+    assert_eq!(outcome.claim_ids, claim_ids);
+    assert!(outcome.revert_reason.is_none());
+    assert!(owner.get_claims().await.is_empty());
+}
+
+#[tokio::test]
+async fn one_failing_claim_rejects_the_whole_batch() {
+    let (owner, claim_ids) = setup().await;
+    let contract_id = owner.contract_id;
+
+    let err = owner
+        .disprove_many(&[claim_ids[0], 999, claim_ids[2]])
+        .await
+        .unwrap_err();
+
+    let DisproveManyError::Rejected(RejectedClaims(rejected)) = err else {
+        panic!("expected rejected claims, got {err}");
+    };
+    assert_eq!(rejected.len(), 1);
+    assert_eq!((rejected[0].0, rejected[0].1), (contract_id, 999));
+
+    // Nothing was submitted, so every claim is still pending rather than some of
+    // them having been disproved.
+    let pending = owner
+        .get_claims()
+        .await
+        .into_iter()
+        .map(|claim| claim.id)
+        .collect::<Vec<_>>();
+    assert_eq!(pending.len(), 3);
+    for claim_id in claim_ids {
+        assert!(pending.contains(&claim_id));
+    }
+}
+
+#[tokio::test]
+async fn every_failing_claim_is_reported() {
+    let (owner, claim_ids) = setup().await;
+
+    let err = owner
+        .disprove_many(&[998, claim_ids[1], 999])
+        .await
+        .unwrap_err();
+
+    let DisproveManyError::Rejected(RejectedClaims(rejected)) = err else {
+        panic!("expected rejected claims, got {err}");
+    };
+    assert_eq!(
+        rejected
+            .iter()
+            .map(|(_, claim_id, _)| *claim_id)
+            .collect::<Vec<_>>(),
+        [998, 999]
+    );
+}
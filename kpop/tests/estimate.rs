@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use fuels::{prelude::*, types::Identity};
+use kpop::Kpop;
+use test_support::Harness;
+
+struct Parties {
+    owner: Kpop,
+    claimant: Kpop,
+}
+
+/// Owner with a funded predicate.
+async fn setup() -> Parties {
+    let harness = Harness::builder().build().await;
+
+    let (owner, claimant) = harness.parties(Kpop::load);
+    owner.fund_predicate(None, 10_000).await.unwrap();
+
+    Parties { owner, claimant }
+}
+
+impl Parties {
+    async fn claim(&self) -> u64 {
+        self.claimant
+            .claim(self.owner.wallet.address().into(), None, 1_000)
+            .await
+            .unwrap()
+            .claim_ids[0]
+    }
+
+    /// Wallet and predicate balances of both parties.
+    async fn balances(&self) -> Vec<HashMap<String, u64>> {
+        vec![
+            self.owner.wallet_balance().await,
+            self.owner.predicate_balance().await,
+            self.claimant.wallet_balance().await,
+            self.claimant.predicate_balance().await,
+        ]
+    }
+}
+
+#[tokio::test]
+async fn estimating_a_transfer_reports_gas_and_fee_without_moving_funds() {
+    let parties = setup().await;
+    let before = parties.balances().await;
+
+    let funding = parties
+        .owner
+        .estimate_fund_predicate(None, 1_000)
+        .await
+        .unwrap();
+    let recipient = Identity::Address(parties.claimant.wallet.address().into());
+    let sending = parties
+        .owner
+        .estimate_send_to(&recipient, None, 1_000)
+        .await
+        .unwrap();
+
+    for estimate in [funding, sending] {
+        assert!(estimate.gas_used > 0);
+        assert!(estimate.total_fee > 0);
+        assert_eq!(estimate.revert_reason, None);
+    }
+    assert_eq!(parties.balances().await, before);
+}
+
+#[tokio::test]
+async fn estimating_a_claim_files_nothing() {
+    let parties = setup().await;
+    let before = parties.balances().await;
+
+    let estimate = parties
+        .claimant
+        .estimate_claim(parties.owner.wallet.address().into(), None, 1_000)
+        .await
+        .unwrap();
+
+    assert!(estimate.gas_used > 0);
+    assert!(estimate.total_fee > 0);
+    assert_eq!(estimate.revert_reason, None);
+    assert!(parties.owner.get_claims().await.is_empty());
+    assert_eq!(parties.balances().await, before);
+}
+
+#[tokio::test]
+async fn estimating_a_disproval_leaves_the_claim_pending() {
+    let parties = setup().await;
+    let claim_id = parties.claim().await;
+    let before = parties.balances().await;
+
+    let estimate = parties
+        .owner
+        .estimate_disprove_claim(claim_id)
+        .await
+        .unwrap();
+
+    assert!(estimate.gas_used > 0);
+    assert!(estimate.total_fee > 0);
+    assert_eq!(estimate.revert_reason, None);
+    assert_eq!(parties.owner.locate_claim(claim_id).await.len(), 1);
+    assert_eq!(parties.balances().await, before);
+}
+
+#[tokio::test]
+async fn estimating_a_reverting_call_reports_the_reason() {
+    let parties = setup().await;
+    let claim_id = parties.claim().await;
+    let before = parties.balances().await;
+
+    let estimate = parties
+        .claimant
+        .estimate_fulfill_claim(claim_id)
+        .await
+        .unwrap();
+
+    let reason = estimate
+        .revert_reason
+        .expect("fulfilling should be too soon");
+    assert!(reason.contains("TooSoon"), "unexpected reason: {reason}");
+    assert!(estimate.gas_used > 0);
+    assert_eq!(parties.owner.locate_claim(claim_id).await.len(), 1);
+    assert_eq!(parties.balances().await, before);
+}
@@ -0,0 +1,75 @@
+use fuels::prelude::*;
+use test_support::Harness;
+
+/// Owner holding the base asset and two others.
+async fn setup() -> (kpop::Kpop, Vec<AssetId>) {
+    let harness = Harness::builder()
+        .wallets(1)
+        .asset(AssetId::new([1; 32]))
+        .asset(AssetId::new([2; 32]))
+        .coins(2, 1_000_000)
+        .build()
+        .await;
+
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+    (kp, harness.assets)
+}
+
+fn balance(balances: &std::collections::HashMap<String, u64>, asset_id: &AssetId) -> u64 {
+    balances.get(&asset_id.to_string()).copied().unwrap_or(0)
+}
+
+#[tokio::test]
+async fn funds_several_assets_in_one_transaction() {
+    let (kp, asset_ids) = setup().await;
+    let others = asset_ids
+        .iter()
+        .filter(|asset_id| **asset_id != AssetId::zeroed())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let outcome = kp
+        .fund_predicate_many(&[
+            (AssetId::zeroed(), 1_000),
+            (others[0], 100),
+            (others[1], 5),
+            (others[1], 5),
+        ])
+        .await
+        .unwrap();
+
+    assert!(outcome.revert_reason.is_none());
+
+    let balances = kp.predicate_balance().await;
+    assert_eq!(balance(&balances, &AssetId::zeroed()), 1_000);
+    assert_eq!(balance(&balances, &others[0]), 100);
+    assert_eq!(balance(&balances, &others[1]), 10);
+}
+
+#[tokio::test]
+async fn all_except_gas_empties_the_wallet_but_the_fee() {
+    let (kp, _) = setup().await;
+    let wallet_before = kp.wallet_balance().await;
+
+    let amounts = kp.all_except_gas().await.unwrap();
+    let outcome = kp.fund_predicate_many(&amounts).await.unwrap();
+
+    let wallet_after = kp.wallet_balance().await;
+    let predicate = kp.predicate_balance().await;
+
+    for (asset_id, amount) in &wallet_before {
+        let asset_id: AssetId = asset_id.parse().unwrap();
+
+        if asset_id == AssetId::zeroed() {
+            let left = balance(&wallet_after, &asset_id);
+            assert_eq!(
+                balance(&predicate, &asset_id) + left + outcome.total_fee,
+                *amount
+            );
+            assert!(left < *amount / 100);
+        } else {
+            assert_eq!(balance(&wallet_after, &asset_id), 0);
+            assert_eq!(balance(&predicate, &asset_id), *amount);
+        }
+    }
+}
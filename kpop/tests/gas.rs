@@ -0,0 +1,124 @@
+use fuels::{
+    prelude::*,
+    tx::{Receipt, TxId},
+    types::transaction_response::TransactionType,
+};
+use kpop::gas::{GasOptions, SAFETY_MULTIPLIER};
+
+#[test]
+fn default_options_leave_policies_unset() {
+    let policies = GasOptions::default().tx_policies();
+
+    assert_eq!(policies.script_gas_limit(), None);
+    assert_eq!(policies.tip(), None);
+    assert_eq!(policies.max_fee(), None);
+}
+
+#[test]
+fn overrides_end_up_in_the_policies() {
+    let gas = GasOptions {
+        gas_limit: Some(100_000),
+        tip: Some(5),
+        max_fee: Some(1_000),
+    };
+    let policies = gas.tx_policies();
+
+    assert_eq!(policies.script_gas_limit(), Some(100_000));
+    assert_eq!(policies.tip(), Some(5));
+    assert_eq!(policies.max_fee(), Some(1_000));
+}
+
+#[test]
+fn estimated_gas_is_scaled_by_the_safety_multiplier() {
+    let gas = GasOptions {
+        tip: Some(5),
+        ..Default::default()
+    };
+    let policies = gas.tx_policies_for(1_001);
+
+    assert_eq!(
+        policies.script_gas_limit(),
+        Some((1_001f64 * SAFETY_MULTIPLIER).ceil() as u64)
+    );
+    assert_eq!(policies.tip(), Some(5));
+}
+
+#[test]
+fn a_gas_limit_override_replaces_the_estimate() {
+    let gas = GasOptions {
+        gas_limit: Some(100_000),
+        ..Default::default()
+    };
+
+    assert_eq!(gas.tx_policies_for(1_000).script_gas_limit(), Some(100_000));
+}
+
+/// Script gas limit of a submitted transaction and the script gas it used.
+async fn script_gas(provider: &Provider, tx_id: TxId) -> (u64, u64) {
+    let response = provider
+        .get_transaction_by_id(&tx_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let TransactionType::Script(tx) = response.transaction else {
+        panic!("expected a script transaction");
+    };
+    let used = response
+        .status
+        .take_receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .unwrap();
+
+    (tx.gas_limit(), used)
+}
+
+async fn setup() -> (test_support::Harness, kpop::Kpop, kpop::Kpop) {
+    let harness = test_support::Harness::builder().build().await;
+
+    let (owner, claimant) = harness.parties(kpop::Kpop::load);
+    owner.fund_predicate(None, 10_000).await.unwrap();
+
+    (harness, owner, claimant)
+}
+
+#[tokio::test]
+async fn contract_calls_are_limited_to_the_scaled_dry_run_gas() {
+    let (harness, owner, claimant) = setup().await;
+    let owner_address: Address = owner.wallet.address().into();
+    let claim_id = claimant
+        .claim(owner_address, None, 1_000)
+        .await
+        .unwrap()
+        .claim_ids[0];
+
+    let disproved = owner.disprove_claim(claim_id).await.unwrap();
+
+    // Chain state is the same as during the dry run, so the script uses exactly
+    // the estimated gas.
+    let (limit, used) = script_gas(&harness.provider, disproved.tx_id).await;
+    assert_eq!(limit, (used as f64 * SAFETY_MULTIPLIER).ceil() as u64);
+}
+
+#[tokio::test]
+async fn a_gas_limit_override_skips_estimation() {
+    let (harness, owner, claimant) = setup().await;
+    let owner_address: Address = owner.wallet.address().into();
+    let claim_id = claimant
+        .claim(owner_address, None, 1_000)
+        .await
+        .unwrap()
+        .claim_ids[0];
+
+    let owner = owner.with_gas_options(GasOptions {
+        gas_limit: Some(1_000_000),
+        ..Default::default()
+    });
+    let disproved = owner.disprove_claim(claim_id).await.unwrap();
+
+    let (limit, _) = script_gas(&harness.provider, disproved.tx_id).await;
+    assert_eq!(limit, 1_000_000);
+}
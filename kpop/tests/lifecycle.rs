@@ -0,0 +1,109 @@
+use fuels::prelude::*;
+use kpop::{claims, error::TxError, CHALLENGE_PERIOD};
+use test_support::Harness;
+
+struct Parties {
+    harness: Harness,
+    owner: kpop::Kpop,
+    claimant: kpop::Kpop,
+}
+
+/// Owner with a funded predicate and a claim against it.
+async fn setup() -> (Parties, kpop::Claim) {
+    let harness = Harness::builder().build().await;
+
+    let (owner, claimant) = harness.parties(kpop::Kpop::load);
+    owner.fund_predicate(None, 10_000).await.unwrap();
+    claimant
+        .claim(owner.wallet.address().into(), None, 1_000)
+        .await
+        .unwrap();
+    let claim = owner.get_claims().await.remove(0);
+
+    let parties = Parties {
+        harness,
+        owner,
+        claimant,
+    };
+    (parties, claim)
+}
+
+async fn contract_balance(kp: &kpop::Kpop) -> u64 {
+    kp.wallet
+        .provider()
+        .unwrap()
+        .get_contract_asset_balance(&kp.contract_id.into(), AssetId::zeroed())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn only_the_owner_can_disprove() {
+    let (parties, claim) = setup().await;
+
+    let err = parties.claimant.disprove_claim(claim.id).await.unwrap_err();
+
+    let TxError::Reverted { reason, .. } = &err else {
+        panic!("expected a revert, got {err:?}");
+    };
+    assert!(
+        reason.starts_with("OnlyOwner"),
+        "unexpected reason {reason}"
+    );
+    assert_eq!(parties.owner.get_claims().await.len(), 1);
+    assert_eq!(contract_balance(&parties.owner).await, 1_000);
+}
+
+#[tokio::test]
+async fn fulfilling_too_soon_reverts() {
+    let (parties, claim) = setup().await;
+
+    let err = parties.claimant.fulfill_claim(claim.id).await.unwrap_err();
+
+    assert_eq!(
+        err,
+        TxError::Reverted {
+            tx_id: err.tx_id().unwrap(),
+            reason: format!("TooSoon({})", claim.block_height + CHALLENGE_PERIOD),
+        }
+    );
+    assert_eq!(parties.owner.get_claims().await.len(), 1);
+    assert_eq!(contract_balance(&parties.owner).await, 1_000);
+}
+
+#[tokio::test]
+async fn disproving_a_fulfilled_claim_reverts() {
+    let (parties, claim) = setup().await;
+    parties
+        .harness
+        .produce_blocks_until_fulfillable(claim.block_height)
+        .await;
+    parties.claimant.fulfill_claim(claim.id).await.unwrap();
+
+    let err = parties.owner.disprove_claim(claim.id).await.unwrap_err();
+
+    assert!(matches!(err, TxError::Reverted { .. }), "{err}");
+    assert!(err.tx_id().is_some());
+    assert!(parties.owner.get_claims().await.is_empty());
+    assert_eq!(contract_balance(&parties.owner).await, 0);
+}
+
+#[tokio::test]
+async fn claims_are_fulfillable_once_no_blocks_are_left() {
+    let (parties, claim) = setup().await;
+
+    let height = parties
+        .harness
+        .produce_blocks_until_fulfillable(claim.block_height)
+        .await;
+    assert_eq!(claims::blocks_left(&claim, height), 0);
+    assert_eq!(claims::blocks_left(&claim, height - 1), 1);
+
+    let outcome = parties.claimant.fulfill_claim(claim.id).await.unwrap();
+
+    assert_eq!(
+        outcome.block_height,
+        Some(claim.block_height + CHALLENGE_PERIOD)
+    );
+    assert!(parties.owner.get_claims().await.is_empty());
+}
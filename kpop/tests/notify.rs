@@ -0,0 +1,176 @@
+use std::net::SocketAddr;
+
+use axum::{extract::State, routing::post, Json, Router};
+use kpop::{
+    claims::ClaimView,
+    notify::{CommandNotifier, EmailNotifier, Notifier, WebhookNotifier},
+    watch::ClaimEvent,
+};
+use serde_json::Value;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+fn filed_event() -> ClaimEvent {
+    ClaimEvent::Filed {
+        block_height: 7,
+        claim: ClaimView {
+            contract_id: "00".repeat(32),
+            id: 3,
+            owner: "fuel1owner".to_string(),
+            recipient: "fuel1recipient".to_string(),
+            asset_id: "00".repeat(32),
+            amount: 10_000,
+            block_height: 7,
+        },
+    }
+}
+
+/// Local HTTP server forwarding every POSTed JSON body to the returned channel.
+async fn stand_in_webhook() -> (SocketAddr, mpsc::UnboundedReceiver<Value>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    let router =
+        Router::new()
+            .route(
+                "/hook",
+                post(
+                    |State(sender): State<mpsc::UnboundedSender<Value>>,
+                     Json(body): Json<Value>| async move {
+                        sender.send(body).unwrap();
+                    },
+                ),
+            )
+            .with_state(sender);
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    (addr, receiver)
+}
+
+/// Local SMTP server accepting a single message and forwarding its data.
+async fn stand_in_smtp() -> (SocketAddr, mpsc::UnboundedReceiver<String>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (read, mut write) = stream.into_split();
+        let mut lines = BufReader::new(read).lines();
+
+        write.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+
+        let mut data = None;
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if let Some(body) = data.as_mut() {
+                if line == "." {
+                    sender.send(std::mem::take(body)).unwrap();
+                    data = None;
+                    write.write_all(b"250 OK\r\n").await.unwrap();
+                } else {
+                    body.push_str(&line);
+                    body.push('\n');
+                }
+                continue;
+            }
+
+            let command = line.to_uppercase();
+            let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+                b"250 localhost\r\n"
+            } else if command.starts_with("DATA") {
+                data = Some(String::new());
+                b"354 End data with <CR><LF>.<CR><LF>\r\n"
+            } else if command.starts_with("QUIT") {
+                write.write_all(b"221 Bye\r\n").await.unwrap();
+                break;
+            } else {
+                b"250 OK\r\n"
+            };
+            write.write_all(reply).await.unwrap();
+        }
+    });
+
+    (addr, receiver)
+}
+
+#[tokio::test]
+async fn webhook_notifier_posts_event_json() {
+    let (addr, mut received) = stand_in_webhook().await;
+    let event = filed_event();
+
+    WebhookNotifier::new(format!("http://{addr}/hook"))
+        .notify(&event)
+        .await
+        .expect("should be able to post to webhook");
+
+    let body = received.recv().await.unwrap();
+    assert_eq!(body, serde_json::to_value(&event).unwrap());
+    assert_eq!(body["type"], "filed");
+}
+
+#[tokio::test]
+async fn webhook_notifier_reports_http_errors() {
+    let (addr, _received) = stand_in_webhook().await;
+
+    let res = WebhookNotifier::new(format!("http://{addr}/missing"))
+        .notify(&filed_event())
+        .await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn email_notifier_sends_event_over_smtp() {
+    let (addr, mut received) = stand_in_smtp().await;
+
+    EmailNotifier {
+        host: addr.ip().to_string(),
+        port: addr.port(),
+        from: "kpop@localhost".to_string(),
+        to: "owner@localhost".to_string(),
+        credentials: None,
+        starttls: false,
+    }
+    .notify(&filed_event())
+    .await
+    .expect("should be able to send email");
+
+    let data = received.recv().await.unwrap();
+    assert!(data.contains("Subject: kpop: claim filed"));
+    assert!(data.contains("\"amount\": 10000"));
+}
+
+#[tokio::test]
+async fn command_notifier_passes_event_on_stdin() {
+    let output = test_support::temp_path("notify");
+    let event = filed_event();
+
+    CommandNotifier {
+        command: format!("cat > {}", output.display()),
+    }
+    .notify(&event)
+    .await
+    .expect("should be able to run command");
+
+    let written: Value = serde_json::from_slice(&std::fs::read(&output).unwrap()).unwrap();
+    std::fs::remove_file(&output).unwrap();
+
+    assert_eq!(written, serde_json::to_value(&event).unwrap());
+}
+
+#[tokio::test]
+async fn command_notifier_reports_failing_commands() {
+    let res = CommandNotifier {
+        command: "exit 3".to_string(),
+    }
+    .notify(&filed_event())
+    .await;
+
+    assert!(res.is_err());
+}
@@ -0,0 +1,142 @@
+use fuels::{crypto::SecretKey, prelude::*, tx::field::Witnesses};
+use kpop::{error::UnsignedTxError, unsigned::UnsignedTransaction};
+use serde_json::Value;
+use test_support::temp_path;
+
+struct Fixture {
+    kp: kpop::Kpop,
+    owner_key: SecretKey,
+    recipient: WalletUnlocked,
+}
+
+/// The online side uses a throwaway wallet, the owner key only ever signs.
+async fn setup() -> Fixture {
+    let harness = test_support::Harness::builder().wallets(2).build().await;
+
+    let (owner_kp, online_kp) = harness.parties(kpop::Kpop::load);
+    owner_kp.fund_predicate(None, 10_000).await.unwrap();
+
+    Fixture {
+        kp: online_kp,
+        owner_key: harness.owner_key(),
+        recipient: harness.claimant().clone(),
+    }
+}
+
+async fn build(fixture: &Fixture, amount: u64) -> UnsignedTransaction {
+    let owner: Address = (*fixture.owner_key.public_key().hash()).into();
+
+    fixture
+        .kp
+        .build_unsigned_send_to(owner, fixture.recipient.address(), None, amount)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn transaction_files_round_trip_through_offline_signing() -> Result<()> {
+    let fixture = setup().await;
+    let unsigned_path = temp_path("unsigned");
+    let signed_path = temp_path("signed");
+
+    let recipient_before = fixture
+        .recipient
+        .get_asset_balance(&AssetId::zeroed())
+        .await?;
+
+    // online
+    build(&fixture, 100).await.save(&unsigned_path).unwrap();
+
+    // offline
+    let mut tx = UnsignedTransaction::load(&unsigned_path).unwrap();
+    assert!(!tx.is_signed());
+    tx.sign(&fixture.owner_key).unwrap();
+    tx.save(&signed_path).unwrap();
+
+    // online
+    let signed = UnsignedTransaction::load(&signed_path).unwrap();
+    assert!(signed.is_signed());
+    let outcome = fixture.kp.submit_signed(signed).await.unwrap();
+
+    std::fs::remove_file(&unsigned_path).unwrap();
+    std::fs::remove_file(&signed_path).unwrap();
+
+    assert_eq!(outcome.tx_id, tx.tx_id);
+    assert_eq!(
+        fixture
+            .recipient
+            .get_asset_balance(&AssetId::zeroed())
+            .await?,
+        recipient_before + 100
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn transaction_file_has_documented_fields() {
+    let fixture = setup().await;
+    let path = temp_path("fields");
+
+    let unsigned = build(&fixture, 100).await;
+    unsigned.save(&path).unwrap();
+
+    let file: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut keys = file.as_object().unwrap().keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, ["chain_id", "owner", "transaction", "tx_id"]);
+    assert_eq!(file["tx_id"], serde_json::to_value(unsigned.tx_id).unwrap());
+}
+
+#[tokio::test]
+async fn only_the_owner_can_sign() {
+    let fixture = setup().await;
+    let recipient_key = test_support::private_key(1);
+
+    let mut tx = build(&fixture, 100).await;
+    let err = tx.sign(&recipient_key).unwrap_err();
+
+    assert_eq!(
+        err,
+        UnsignedTxError::WrongSigner {
+            owner: tx.owner,
+            signer: (*recipient_key.public_key().hash()).into(),
+        }
+    );
+    assert!(!tx.is_signed());
+}
+
+#[tokio::test]
+async fn signing_refuses_tampered_transactions() {
+    let fixture = setup().await;
+
+    let mut tx = build(&fixture, 100).await;
+    tx.chain_id += 1;
+
+    let err = tx.sign(&fixture.owner_key).unwrap_err();
+
+    assert!(matches!(err, UnsignedTxError::IdMismatch { tx_id, .. } if tx_id == tx.tx_id));
+    assert!(!tx.is_signed());
+}
+
+#[test]
+fn unreadable_transaction_files_are_an_error() {
+    let path = temp_path("missing");
+
+    assert!(matches!(
+        UnsignedTransaction::load(&path),
+        Err(UnsignedTxError::Unreadable { .. })
+    ));
+}
+
+#[tokio::test]
+async fn a_transaction_without_witnesses_is_not_signed() {
+    let fixture = setup().await;
+
+    let mut tx = build(&fixture, 100).await;
+    tx.transaction.witnesses_mut().clear();
+
+    assert!(!tx.is_signed());
+}
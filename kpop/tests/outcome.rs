@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use fuels::{prelude::*, tx::TxId};
+use kpop::error::TxError;
+
+struct Parties {
+    owner: kpop::Kpop,
+    claimant: kpop::Kpop,
+}
+
+async fn setup() -> Parties {
+    let harness = test_support::Harness::builder().wallets(2).build().await;
+    let (owner, claimant) = harness.parties(kpop::Kpop::load);
+
+    Parties { owner, claimant }
+}
+
+#[tokio::test]
+async fn outcome_reports_block_fee_and_gas() {
+    let parties = setup().await;
+
+    let outcome = parties.owner.fund_predicate(None, 10_000).await.unwrap();
+
+    assert!(outcome.block_height.is_some());
+    assert!(outcome.total_fee > 0);
+    assert!(outcome.gas_used > 0);
+    assert!(outcome.claim_ids.is_empty());
+    assert!(outcome.revert_reason.is_none());
+    assert!(!outcome.receipts.is_empty());
+}
+
+#[tokio::test]
+async fn claim_operations_report_claim_ids() {
+    let parties = setup().await;
+    parties.owner.fund_predicate(None, 10_000).await.unwrap();
+    let owner: Address = parties.owner.wallet.address().into();
+
+    let filed = parties.claimant.claim(owner, None, 1_000).await.unwrap();
+    assert_eq!(filed.claim_ids.len(), 1);
+
+    let disproved = parties
+        .owner
+        .disprove_claim(filed.claim_ids[0])
+        .await
+        .unwrap();
+    assert_eq!(disproved.claim_ids, filed.claim_ids);
+    assert!(disproved.block_height > filed.block_height);
+}
+
+#[tokio::test]
+async fn past_transactions_can_be_looked_up() {
+    let parties = setup().await;
+
+    let outcome = parties.owner.fund_predicate(None, 10_000).await.unwrap();
+    let status = parties
+        .claimant
+        .tx_status(outcome.tx_id)
+        .await
+        .expect("transaction should be known");
+
+    assert_eq!(status.tx_id, outcome.tx_id);
+    assert_eq!(status.block_height, outcome.block_height);
+    assert_eq!(status.total_fee, outcome.total_fee);
+    assert_eq!(status.gas_used, outcome.gas_used);
+
+    assert!(parties.owner.tx_status(TxId::zeroed()).await.is_none());
+}
+
+#[tokio::test]
+async fn transactions_submitted_without_waiting_can_be_awaited() {
+    let parties = setup().await;
+
+    let txid = parties
+        .owner
+        .fund_predicate_no_wait(None, 10_000)
+        .await
+        .unwrap();
+    let outcome = parties
+        .owner
+        .await_tx(txid, Duration::from_secs(10))
+        .await
+        .expect("transaction should be included");
+
+    assert_eq!(outcome.tx_id, txid);
+    assert!(outcome.block_height.is_some());
+    assert_eq!(
+        parties.owner.predicate_balance().await[&AssetId::zeroed().to_string()],
+        10_000
+    );
+}
+
+#[tokio::test]
+async fn awaiting_a_reverted_transaction_is_an_error() {
+    let parties = setup().await;
+    let contract_id = parties.owner.contract_id;
+
+    let txid = parties
+        .owner
+        .disprove_claim_in_no_wait(contract_id, 42)
+        .await
+        .unwrap();
+    let err = parties
+        .owner
+        .await_tx(txid, Duration::from_secs(10))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TxError::Reverted { .. }));
+    assert_eq!(err.tx_id(), Some(txid));
+}
+
+#[tokio::test]
+async fn awaiting_an_unknown_transaction_times_out() {
+    let parties = setup().await;
+
+    let err = parties
+        .owner
+        .await_tx(TxId::zeroed(), Duration::from_millis(500))
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        TxError::Timeout {
+            tx_id: TxId::zeroed()
+        }
+    );
+}
+
+#[tokio::test]
+async fn funding_more_than_the_wallet_holds_submits_nothing() {
+    let parties = setup().await;
+
+    let err = parties
+        .owner
+        .fund_predicate(None, u64::MAX)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TxError::NotSubmitted { .. }), "{err}");
+    assert_eq!(err.tx_id(), None);
+    assert!(parties.owner.predicate_balance().await.is_empty());
+}
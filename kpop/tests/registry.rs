@@ -0,0 +1,90 @@
+use fuels::prelude::*;
+use kpop::{registry::Registry, Kpop};
+use test_support::{temp_path, Harness};
+
+/// Owner with the harness contract active and a second contract in the registry.
+/// The claimant filed claim 0 in the first contract and claims 0 and 1 in the
+/// second one.
+async fn setup() -> (Harness, Kpop, ContractId) {
+    let harness = Harness::builder().build().await;
+
+    let second: ContractId = Contract::load_from(
+        test_support::CLAIMS_CONTRACT_BINARY,
+        LoadConfiguration::default().with_salt([1; 32]),
+    )
+    .unwrap()
+    .deploy(harness.owner(), TxPolicies::default())
+    .await
+    .unwrap()
+    .into();
+
+    let owner_address: Address = harness.owner().address().into();
+    for (contract_id, claims) in [(harness.contract_id(), 1), (second, 2)] {
+        Kpop::load(harness.provider.clone(), harness.owner_key(), contract_id)
+            .fund_predicate(None, 10_000)
+            .await
+            .unwrap();
+
+        let claimant = Kpop::load(
+            harness.provider.clone(),
+            harness.claimant_key(),
+            contract_id,
+        );
+        for _ in 0..claims {
+            claimant.claim(owner_address, None, 1_000).await.unwrap();
+        }
+    }
+
+    let mut registry = Registry::load(temp_path("registry-two-contracts"));
+    registry.add(second);
+    let owner = harness
+        .load(harness.owner_key(), Kpop::load)
+        .with_registry(registry);
+
+    (harness, owner, second)
+}
+
+#[tokio::test]
+async fn claims_are_read_from_every_registered_contract() {
+    let (harness, owner, second) = setup().await;
+
+    let mut claims = owner
+        .get_all_claims_of(owner.wallet.address().into())
+        .await
+        .into_iter()
+        .map(|(contract_id, claim)| (contract_id, claim.id))
+        .collect::<Vec<_>>();
+    claims.sort();
+
+    let mut expected = vec![(harness.contract_id(), 0), (second, 0), (second, 1)];
+    expected.sort();
+    assert_eq!(claims, expected);
+}
+
+#[tokio::test]
+async fn claims_are_located_in_the_contracts_holding_them() {
+    let (harness, owner, second) = setup().await;
+
+    let mut both = owner.locate_claim(0).await;
+    both.sort();
+    let mut expected = vec![harness.contract_id(), second];
+    expected.sort();
+    assert_eq!(both, expected);
+
+    assert_eq!(owner.locate_claim(1).await, [second]);
+    assert!(owner.locate_claim(2).await.is_empty());
+}
+
+#[tokio::test]
+async fn claims_are_located_by_owner() {
+    let (harness, owner, second) = setup().await;
+    let claimant: Address = harness.claimant().address().into();
+
+    assert_eq!(
+        owner
+            .locate_claim_of(owner.wallet.address().into(), 1)
+            .await,
+        [second]
+    );
+    assert!(owner.locate_claim_of(claimant, 1).await.is_empty());
+}
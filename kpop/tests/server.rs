@@ -0,0 +1,308 @@
+use std::{collections::HashMap, str::FromStr};
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use fuels::{crypto::SecretKey, prelude::*};
+use http_body_util::BodyExt;
+use kpop::{server, unsigned::UnsignedTransaction};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+const TOKEN: &str = "test-token";
+
+struct Fixture {
+    router: Router,
+    owner_key: SecretKey,
+    owner: WalletUnlocked,
+    recipient: WalletUnlocked,
+}
+
+async fn setup() -> Fixture {
+    let harness = test_support::Harness::builder().wallets(2).build().await;
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+
+    Fixture {
+        router: server::router(kp, Some(TOKEN.to_string())),
+        owner_key: harness.owner_key(),
+        owner: harness.owner().clone(),
+        recipient: harness.claimant().clone(),
+    }
+}
+
+/// Send a request with the right bearer token.
+async fn request(
+    router: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, Value) {
+    request_with_token(router, method, uri, body, Some(TOKEN)).await
+}
+
+async fn request_with_token(
+    router: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+    token: Option<&str>,
+) -> (StatusCode, Value) {
+    let body = match body {
+        Some(body) => Body::from(body.to_string()),
+        None => Body::empty(),
+    };
+
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header("authorization", format!("Bearer {token}"));
+    }
+
+    let response = router
+        .clone()
+        .oneshot(builder.body(body).unwrap())
+        .await
+        .unwrap();
+
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body = if bytes.is_empty() {
+        Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+
+    (status, body)
+}
+
+fn base_asset_balance(balances: &Value) -> u64 {
+    let balances: HashMap<String, u64> = serde_json::from_value(balances.clone()).unwrap();
+
+    balances
+        .iter()
+        .find(|(asset_id, _)| AssetId::from_str(asset_id).unwrap() == AssetId::zeroed())
+        .map(|(_, amount)| *amount)
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn serves_openapi_description() {
+    let fixture = setup().await;
+
+    let (status, body) = request(&fixture.router, "GET", "/openapi.json", None).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["paths"]["/balances"].is_object());
+}
+
+#[tokio::test]
+async fn openapi_describes_every_route() {
+    let fixture = setup().await;
+    let description: Value = serde_json::from_str(server::OPENAPI).unwrap();
+    let paths = description["paths"].as_object().unwrap();
+
+    let mut described = paths.keys().map(String::as_str).collect::<Vec<_>>();
+    let mut routed = [server::PUBLIC_PATHS.as_slice(), &server::SIGNING_PATHS].concat();
+    described.sort();
+    routed.sort();
+    assert_eq!(described, routed);
+
+    for (path, methods) in paths {
+        for method in methods.as_object().unwrap().keys() {
+            let request = Request::builder()
+                .method(method.to_uppercase().as_str())
+                .uri(path)
+                .header("authorization", format!("Bearer {TOKEN}"))
+                .body(Body::empty())
+                .unwrap();
+            let status = fixture
+                .router
+                .clone()
+                .oneshot(request)
+                .await
+                .unwrap()
+                .status();
+
+            assert_ne!(status, StatusCode::NOT_FOUND, "{method} {path}");
+            assert_ne!(status, StatusCode::METHOD_NOT_ALLOWED, "{method} {path}");
+        }
+    }
+}
+
+#[tokio::test]
+async fn fund_moves_funds_into_predicate() {
+    let fixture = setup().await;
+
+    let (status, balances) = request(&fixture.router, "GET", "/balances", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(base_asset_balance(&balances["predicate"]), 0);
+    assert_eq!(
+        balances["wallet_address"],
+        fixture.owner.address().to_string()
+    );
+
+    let (status, _) = request(
+        &fixture.router,
+        "POST",
+        "/fund",
+        Some(json!({ "amount": 1000 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, balances) = request(&fixture.router, "GET", "/balances", None).await;
+    assert_eq!(base_asset_balance(&balances["predicate"]), 1000);
+}
+
+#[tokio::test]
+async fn unsigned_send_can_be_signed_and_submitted() -> Result<()> {
+    let fixture = setup().await;
+
+    request(
+        &fixture.router,
+        "POST",
+        "/fund",
+        Some(json!({ "amount": 10_000 })),
+    )
+    .await;
+
+    let recipient_before = fixture
+        .recipient
+        .get_asset_balance(&AssetId::zeroed())
+        .await?;
+
+    let (status, unsigned) = request(
+        &fixture.router,
+        "POST",
+        "/send/unsigned",
+        Some(json!({
+            "owner": fixture.owner.address().to_string(),
+            "recipient": fixture.recipient.address().to_string(),
+            "amount": 100,
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let mut tx: UnsignedTransaction = serde_json::from_value(unsigned).unwrap();
+    assert!(!tx.is_signed());
+    tx.sign(&fixture.owner_key).unwrap();
+
+    let (status, _) = request(
+        &fixture.router,
+        "POST",
+        "/submit",
+        Some(serde_json::to_value(&tx).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(
+        fixture
+            .recipient
+            .get_asset_balance(&AssetId::zeroed())
+            .await?,
+        recipient_before + 100
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn submit_rejects_unsigned_transactions() {
+    let fixture = setup().await;
+
+    request(
+        &fixture.router,
+        "POST",
+        "/fund",
+        Some(json!({ "amount": 10_000 })),
+    )
+    .await;
+
+    let (_, unsigned) = request(
+        &fixture.router,
+        "POST",
+        "/send/unsigned",
+        Some(json!({
+            "owner": fixture.owner.address().to_string(),
+            "recipient": fixture.recipient.address().to_string(),
+            "amount": 100,
+        })),
+    )
+    .await;
+
+    let (status, body) = request(&fixture.router, "POST", "/submit", Some(unsigned)).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "transaction has not been signed");
+}
+
+#[tokio::test]
+async fn failed_operations_are_reported_as_errors() {
+    let fixture = setup().await;
+
+    let (status, body) = request(
+        &fixture.router,
+        "POST",
+        "/disprove",
+        Some(json!({ "claim_id": 42 })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"].as_str().unwrap().contains("reverted"));
+    assert!(body["tx_id"].is_string());
+}
+
+#[tokio::test]
+async fn signing_endpoints_require_the_token() {
+    let fixture = setup().await;
+    let fund = json!({ "amount": 1000 });
+
+    for token in [None, Some("wrong-token")] {
+        let (status, body) =
+            request_with_token(&fixture.router, "POST", "/fund", Some(fund.clone()), token).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(body["error"].is_string());
+    }
+
+    let (_, balances) = request_with_token(&fixture.router, "GET", "/balances", None, None).await;
+    assert_eq!(base_asset_balance(&balances["predicate"]), 0);
+}
+
+#[tokio::test]
+async fn without_a_token_only_the_unsigned_flow_is_served() {
+    let harness = test_support::Harness::builder().wallets(2).build().await;
+    let kp = harness.load(harness.owner_key(), kpop::Kpop::load);
+    let router = server::router(kp, None);
+
+    let (status, _) = request_with_token(
+        &router,
+        "POST",
+        "/fund",
+        Some(json!({ "amount": 1000 })),
+        Some(TOKEN),
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = request_with_token(
+        &router,
+        "POST",
+        "/send/unsigned",
+        Some(json!({
+            "owner": harness.owner().address().to_string(),
+            "recipient": harness.claimant().address().to_string(),
+            "amount": 100,
+        })),
+        None,
+    )
+    .await;
+    // Routed, whether or not there's anything to spend yet.
+    assert_ne!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,189 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use fuels::prelude::*;
+use kpop::{
+    address::AddressBook,
+    assets::AssetRegistry,
+    claims,
+    tui::{Command, Dashboard, Pane, Snapshot},
+    Claim, CHALLENGE_PERIOD,
+};
+use ratatui::{backend::TestBackend, Terminal};
+use test_support::{temp_path, Harness};
+
+fn claim(id: u64, block_height: u32) -> Claim {
+    Claim {
+        id,
+        owner: Address::from([1; 32]),
+        recipient: Address::from([2; 32]),
+        asset: AssetId::zeroed(),
+        amount: 1_000,
+        block_height,
+    }
+}
+
+fn dashboard(height: u32) -> Dashboard {
+    let mut dashboard = Dashboard::new(
+        AssetRegistry::load(temp_path("tui-no-assets")),
+        AddressBook::load(temp_path("tui-no-contacts")),
+    );
+    dashboard.update(Snapshot {
+        height,
+        against: vec![(ContractId::zeroed(), claim(1, 10))],
+        filed: vec![(ContractId::zeroed(), claim(2, 10))],
+        ..Default::default()
+    });
+    dashboard
+}
+
+fn press(dashboard: &mut Dashboard, code: KeyCode) -> Option<Command> {
+    dashboard.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+fn render(dashboard: &mut Dashboard) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    terminal.draw(|frame| dashboard.render(frame)).unwrap();
+
+    terminal
+        .backend()
+        .buffer()
+        .content
+        .iter()
+        .map(|cell| cell.symbol())
+        .collect()
+}
+
+#[test]
+fn countdown_ends_with_the_challenge_period() {
+    let claim = claim(1, 10);
+
+    assert_eq!(claims::blocks_left(&claim, 10), CHALLENGE_PERIOD - 1);
+    assert_eq!(claims::blocks_left(&claim, 10 + CHALLENGE_PERIOD - 2), 1);
+    assert_eq!(claims::blocks_left(&claim, 10 + CHALLENGE_PERIOD - 1), 0);
+    assert_eq!(claims::blocks_left(&claim, 1_000), 0);
+}
+
+#[test]
+fn selected_claim_against_the_wallet_is_disproved() {
+    let mut dashboard = dashboard(20);
+
+    assert_eq!(
+        press(&mut dashboard, KeyCode::Char('d')),
+        Some(Command::Disprove {
+            contract_id: ContractId::zeroed(),
+            claim_id: 1
+        })
+    );
+    assert_eq!(press(&mut dashboard, KeyCode::Char('f')), None);
+    assert_eq!(dashboard.status(), "Only your own claims can be fulfilled");
+}
+
+#[test]
+fn filed_claims_are_only_fulfilled_when_ready() {
+    let mut dashboard = dashboard(20);
+    press(&mut dashboard, KeyCode::Tab);
+    assert_eq!(dashboard.pane(), Pane::Filed);
+
+    assert_eq!(press(&mut dashboard, KeyCode::Char('f')), None);
+    assert_eq!(
+        dashboard.status(),
+        format!(
+            "Claim 2 can be fulfilled in {} blocks",
+            CHALLENGE_PERIOD - 11
+        )
+    );
+
+    dashboard.update(Snapshot {
+        height: 10 + CHALLENGE_PERIOD,
+        ..dashboard.snapshot().unwrap().clone()
+    });
+    assert_eq!(
+        press(&mut dashboard, KeyCode::Char('f')),
+        Some(Command::Fulfill {
+            contract_id: ContractId::zeroed(),
+            claim_id: 2
+        })
+    );
+}
+
+#[test]
+fn fund_amount_is_typed_into_a_prompt() {
+    let mut dashboard = dashboard(20);
+
+    assert_eq!(press(&mut dashboard, KeyCode::Char('a')), None);
+    for c in "1.5 ETHx".chars() {
+        press(&mut dashboard, KeyCode::Char(c));
+    }
+    press(&mut dashboard, KeyCode::Backspace);
+    // Keys go to the prompt, not to the dashboard.
+    assert!(render(&mut dashboard).contains("1.5 ETH_"));
+
+    assert_eq!(
+        press(&mut dashboard, KeyCode::Enter),
+        Some(Command::Fund("1.5 ETH".to_string()))
+    );
+
+    press(&mut dashboard, KeyCode::Char('a'));
+    press(&mut dashboard, KeyCode::Char('1'));
+    assert_eq!(press(&mut dashboard, KeyCode::Esc), None);
+    assert_eq!(
+        press(&mut dashboard, KeyCode::Char('q')),
+        Some(Command::Quit)
+    );
+}
+
+#[test]
+fn claims_are_shown_with_their_countdown() {
+    let mut dashboard = dashboard(20);
+
+    let screen = render(&mut dashboard);
+
+    assert!(screen.contains(&format!(
+        "#1 {} blocks left to disprove: 1000 of",
+        CHALLENGE_PERIOD - 11
+    )));
+    assert!(screen.contains(&format!(
+        "#2 fulfillable in {} blocks: 1000 of",
+        CHALLENGE_PERIOD - 11
+    )));
+}
+
+#[tokio::test]
+async fn snapshot_has_claims_against_and_by_the_wallet() {
+    let harness = Harness::builder().wallets(2).build().await;
+
+    let (owner, claimant) = harness.parties(kpop::Kpop::load);
+    owner.fund_predicate(None, 10_000).await.unwrap();
+    let owner_address: Address = owner.wallet.address().into();
+    let claim_id = claimant
+        .claim(owner_address, None, 1_000)
+        .await
+        .unwrap()
+        .claim_ids[0];
+
+    let owner_view = Snapshot::load(&owner, &[]).await.unwrap();
+    let claimant_view = Snapshot::load(&claimant, &[owner_address]).await.unwrap();
+
+    assert_eq!(owner_view.against.len(), 1);
+    assert_eq!(owner_view.against[0].1.id, claim_id);
+    assert!(owner_view.filed.is_empty());
+
+    assert!(claimant_view.against.is_empty());
+    assert_eq!(claimant_view.filed.len(), 1);
+    assert_eq!(claimant_view.filed[0].1.id, claim_id);
+}
+
+#[test]
+fn claims_past_their_challenge_period_can_still_be_disproved() {
+    let mut dashboard = dashboard(10 + CHALLENGE_PERIOD);
+
+    let screen = render(&mut dashboard);
+    assert!(screen.contains("#1 challenge period over, disprovable until fulfilled"));
+
+    assert_eq!(
+        press(&mut dashboard, KeyCode::Char('d')),
+        Some(Command::Disprove {
+            contract_id: ContractId::zeroed(),
+            claim_id: 1
+        })
+    );
+}
@@ -0,0 +1,185 @@
+use std::{pin::Pin, time::Duration};
+
+use fuels::prelude::*;
+use futures::{Stream, StreamExt};
+use kpop::{claims::ClaimView, watch::ClaimEvent, Kpop};
+use test_support::Harness;
+
+/// Long enough for the first read to finish before the watcher goes to sleep.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+type Events = Pin<Box<dyn Stream<Item = ClaimEvent> + Send>>;
+
+struct Parties {
+    harness: Harness,
+    owner: Kpop,
+    claimant: Kpop,
+}
+
+async fn setup() -> Parties {
+    let harness = Harness::builder().build().await;
+
+    let (owner, claimant) = harness.parties(Kpop::load);
+
+    Parties {
+        harness,
+        owner,
+        claimant,
+    }
+}
+
+impl Parties {
+    /// Follow the owner, waiting until the watcher has read the current state so
+    /// that everything from here on is reported.
+    ///
+    /// The stream only polls the node while it's being awaited, so everything done
+    /// between two calls to [`next`] is seen in a single poll.
+    async fn watch(&self) -> Events {
+        let mut events: Events = Box::pin(
+            self.owner
+                .watch(self.owner.wallet.address().into(), POLL_INTERVAL),
+        );
+
+        let first = tokio::time::timeout(POLL_INTERVAL / 2, events.next()).await;
+        assert!(
+            first.is_err(),
+            "nothing should be reported before the first read"
+        );
+
+        events
+    }
+
+    async fn claim(&self, amount: u64) -> u64 {
+        self.claimant
+            .claim(self.owner.wallet.address().into(), None, amount)
+            .await
+            .unwrap()
+            .claim_ids[0]
+    }
+
+    fn view(&self, claim_id: u64, amount: u64, block_height: u32) -> ClaimView {
+        ClaimView {
+            contract_id: self.harness.contract_id().to_string(),
+            id: claim_id,
+            owner: self.owner.wallet.address().to_string(),
+            recipient: self.claimant.wallet.address().to_string(),
+            asset_id: AssetId::zeroed().to_string(),
+            amount,
+            block_height,
+        }
+    }
+
+    async fn height(&self) -> u32 {
+        self.harness.provider.latest_block_height().await.unwrap()
+    }
+}
+
+async fn next(events: &mut Events) -> ClaimEvent {
+    tokio::time::timeout(Duration::from_secs(10), events.next())
+        .await
+        .expect("an event should be reported")
+        .expect("the stream shouldn't end")
+}
+
+fn balance_event(block_height: u32, balance: u64) -> ClaimEvent {
+    ClaimEvent::PredicateBalance {
+        block_height,
+        asset_id: AssetId::zeroed().to_string(),
+        balance,
+    }
+}
+
+#[tokio::test]
+async fn funding_the_predicate_is_reported() {
+    let parties = setup().await;
+    let mut events = parties.watch().await;
+
+    parties.owner.fund_predicate(None, 10_000).await.unwrap();
+    let funded_at = parties.height().await;
+
+    assert_eq!(next(&mut events).await, balance_event(funded_at, 10_000));
+}
+
+#[tokio::test]
+async fn filed_and_disproved_claims_are_reported() {
+    let parties = setup().await;
+    parties.owner.fund_predicate(None, 10_000).await.unwrap();
+    let mut events = parties.watch().await;
+
+    let claim_id = parties.claim(1_000).await;
+    let filed_at = parties.height().await;
+
+    assert_eq!(
+        next(&mut events).await,
+        ClaimEvent::Filed {
+            block_height: filed_at,
+            claim: parties.view(claim_id, 1_000, filed_at),
+        }
+    );
+    assert_eq!(next(&mut events).await, balance_event(filed_at, 9_000));
+
+    parties.owner.disprove_claim(claim_id).await.unwrap();
+    let disproved_at = parties.height().await;
+
+    assert_eq!(
+        next(&mut events).await,
+        ClaimEvent::Disproved {
+            block_height: disproved_at,
+            claim: parties.view(claim_id, 1_000, filed_at),
+        }
+    );
+}
+
+#[tokio::test]
+async fn fulfilled_claims_are_reported() {
+    let parties = setup().await;
+    parties.owner.fund_predicate(None, 10_000).await.unwrap();
+
+    let claim_id = parties.claim(1_000).await;
+    let filed_at = parties.height().await;
+    let mut events = parties.watch().await;
+
+    parties
+        .harness
+        .produce_blocks_until_fulfillable(filed_at)
+        .await;
+    parties.claimant.fulfill_claim(claim_id).await.unwrap();
+    let fulfilled_at = parties.height().await;
+
+    assert_eq!(
+        next(&mut events).await,
+        ClaimEvent::Fulfilled {
+            block_height: fulfilled_at,
+            claim: parties.view(claim_id, 1_000, filed_at),
+        }
+    );
+}
+
+#[tokio::test]
+async fn claims_filed_and_disproved_between_polls_are_reported() {
+    let parties = setup().await;
+    parties.owner.fund_predicate(None, 10_000).await.unwrap();
+    let mut events = parties.watch().await;
+
+    let claim_id = parties.claim(1_000).await;
+    let filed_at = parties.height().await;
+    parties.owner.disprove_claim(claim_id).await.unwrap();
+    let disproved_at = parties.height().await;
+
+    let claim = parties.view(claim_id, 1_000, filed_at);
+    assert_eq!(
+        next(&mut events).await,
+        ClaimEvent::Filed {
+            block_height: filed_at,
+            claim: claim.clone(),
+        }
+    );
+    assert_eq!(
+        next(&mut events).await,
+        ClaimEvent::Disproved {
+            block_height: disproved_at,
+            claim,
+        }
+    );
+    assert_eq!(next(&mut events).await, balance_event(disproved_at, 9_000));
+}
@@ -0,0 +1,24 @@
+//! The fixtures load the outputs of the sway projects, which are built by `forc`
+//! (the claims contract and predicate crates do so in their own build scripts).
+//! This only checks that they exist, and rebuilds the bindings when they change.
+
+use std::path::Path;
+
+const OUTPUTS: [&str; 6] = [
+    "../claims-contract/out/debug/claims-contract.bin",
+    "../claims-contract/out/debug/claims-contract-abi.json",
+    "../make-claim/out/debug/make-claim.bin",
+    "../make-claim/out/debug/make-claim-abi.json",
+    "../claimable/out/debug/claimable.bin",
+    "../claimable/out/debug/claimable-abi.json",
+];
+
+fn main() {
+    for output in OUTPUTS {
+        assert!(
+            Path::new(output).exists(),
+            "{output} is missing, run `forc build` in its project first"
+        );
+        println!("cargo:rerun-if-changed={output}");
+    }
+}
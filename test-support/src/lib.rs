@@ -0,0 +1,350 @@
+//! Fixtures shared by the contract, predicate and kpop integration tests.
+//!
+//! [`Harness::builder`] starts a node with funded wallets and deploys the claims
+//! contract. The make-claim script and the claimable predicate are configured on
+//! demand, so tests of the contract alone don't need them built.
+//!
+//! Binaries are found relative to this crate, so the fixtures work the same from
+//! every crate's tests.
+
+use std::path::PathBuf;
+
+use fuels::{
+    client::{FuelClient, PageDirection, PaginationRequest},
+    crypto::SecretKey,
+    prelude::*,
+    types::Bits256,
+};
+
+use fuel_core_client::client::types::TransactionStatus;
+
+pub use kpop_core::CHALLENGE_PERIOD;
+
+pub mod claimable_predicate {
+    use fuels::prelude::*;
+
+    abigen!(Predicate(
+        name = "Claimable",
+        abi = "../claimable/out/debug/claimable-abi.json"
+    ));
+}
+
+pub mod claims_contract {
+    use fuels::prelude::*;
+
+    abigen!(Contract(
+        name = "ClaimsContract",
+        abi = "../claims-contract/out/debug/claims-contract-abi.json"
+    ));
+}
+
+pub mod make_claim_script {
+    use fuels::prelude::*;
+
+    abigen!(Script(
+        name = "MakeClaim",
+        abi = "../make-claim/out/debug/make-claim-abi.json",
+    ));
+}
+
+use claimable_predicate::ClaimableConfigurables;
+use claims_contract::ClaimsContract;
+use make_claim_script::{MakeClaim, MakeClaimConfigurables};
+
+pub const CLAIMS_CONTRACT_BINARY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../claims-contract/out/debug/claims-contract.bin"
+);
+pub const MAKE_CLAIM_SCRIPT_BINARY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../make-claim/out/debug/make-claim.bin"
+);
+pub const CLAIMABLE_PREDICATE_BINARY: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../claimable/out/debug/claimable.bin"
+);
+
+/// Keys of the first wallets, the same ones the devnet funds.
+pub use kpop_core::PREFUNDED_KEYS as PRIVATE_KEYS;
+
+/// Key of wallet `index`, one of [`PRIVATE_KEYS`] or a made up one after those.
+pub fn private_key(index: usize) -> SecretKey {
+    match PRIVATE_KEYS.get(index) {
+        Some(key) => key.parse().unwrap(),
+        None => SecretKey::try_from(&[index as u8; 32][..]).unwrap(),
+    }
+}
+
+/// A file path in the temp dir, unique to `name` and the test process. The file
+/// isn't created.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("kpop-{name}-{}.json", std::process::id()))
+}
+
+/// An empty directory in the temp dir, unique to `name` and the test process.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("kpop-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("should be able to create temp dir");
+    dir
+}
+
+pub struct HarnessBuilder {
+    wallets: usize,
+    assets: Vec<AssetId>,
+    coins: u64,
+    amount_per_coin: u64,
+    owner: usize,
+    claimant: usize,
+}
+
+impl Default for HarnessBuilder {
+    fn default() -> Self {
+        Self {
+            wallets: 3,
+            assets: vec![AssetId::zeroed()],
+            coins: 10,
+            amount_per_coin: 1_000_000,
+            owner: 0,
+            claimant: 1,
+        }
+    }
+}
+
+impl HarnessBuilder {
+    pub fn wallets(mut self, wallets: usize) -> Self {
+        self.wallets = wallets;
+        self
+    }
+
+    /// Fund every wallet with `asset_id` as well as the base asset.
+    pub fn asset(mut self, asset_id: AssetId) -> Self {
+        if !self.assets.contains(&asset_id) {
+            self.assets.push(asset_id);
+        }
+        self
+    }
+
+    /// Coins of each asset per wallet.
+    pub fn coins(mut self, coins: u64, amount_per_coin: u64) -> Self {
+        self.coins = coins;
+        self.amount_per_coin = amount_per_coin;
+        self
+    }
+
+    /// Wallet that deploys the contract and owns the predicate.
+    pub fn owner(mut self, index: usize) -> Self {
+        self.owner = index;
+        self
+    }
+
+    /// Wallet that runs the make-claim script.
+    pub fn claimant(mut self, index: usize) -> Self {
+        self.claimant = index;
+        self
+    }
+
+    pub async fn build(self) -> Harness {
+        let keys = (0..self.wallets).map(private_key).collect::<Vec<_>>();
+
+        let coins = keys
+            .iter()
+            .flat_map(|key| {
+                let wallet = WalletUnlocked::new_from_private_key(*key, None);
+                self.assets
+                    .iter()
+                    .flat_map(|asset_id| {
+                        setup_single_asset_coins(
+                            wallet.address(),
+                            *asset_id,
+                            self.coins,
+                            self.amount_per_coin,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let provider = setup_test_provider(coins, vec![], Some(NodeConfig::default()), None)
+            .await
+            .unwrap();
+
+        let wallets = keys
+            .iter()
+            .map(|key| WalletUnlocked::new_from_private_key(*key, Some(provider.clone())))
+            .collect::<Vec<_>>();
+
+        let id = Contract::load_from(CLAIMS_CONTRACT_BINARY, LoadConfiguration::default())
+            .unwrap()
+            .deploy(&wallets[self.owner], TxPolicies::default())
+            .await
+            .unwrap();
+        let contract = ClaimsContract::new(id, wallets[self.owner].clone());
+
+        Harness {
+            provider,
+            keys,
+            wallets,
+            assets: self.assets,
+            contract,
+            owner: self.owner,
+            claimant: self.claimant,
+        }
+    }
+}
+
+pub struct Harness {
+    pub provider: Provider,
+    pub keys: Vec<SecretKey>,
+    pub wallets: Vec<WalletUnlocked>,
+    /// The base asset first.
+    pub assets: Vec<AssetId>,
+    /// Called as the owner.
+    pub contract: ClaimsContract<WalletUnlocked>,
+    owner: usize,
+    claimant: usize,
+}
+
+impl Harness {
+    pub fn builder() -> HarnessBuilder {
+        HarnessBuilder::default()
+    }
+
+    pub fn owner(&self) -> &WalletUnlocked {
+        &self.wallets[self.owner]
+    }
+
+    pub fn owner_key(&self) -> SecretKey {
+        self.keys[self.owner]
+    }
+
+    pub fn claimant(&self) -> &WalletUnlocked {
+        &self.wallets[self.claimant]
+    }
+
+    pub fn claimant_key(&self) -> SecretKey {
+        self.keys[self.claimant]
+    }
+
+    pub fn contract_id(&self) -> ContractId {
+        self.contract.contract_id().into()
+    }
+
+    /// A client for the wallet of `key` and the deployed contract, made by `load`,
+    /// e.g. `kpop::Kpop::load`. This crate can't depend on kpop, so the caller
+    /// passes the constructor.
+    pub fn load<T>(
+        &self,
+        key: SecretKey,
+        load: impl FnOnce(Provider, SecretKey, ContractId) -> T,
+    ) -> T {
+        load(self.provider.clone(), key, self.contract_id())
+    }
+
+    /// Clients of the owner and the claimant, see [`Harness::load`].
+    pub fn parties<T>(&self, load: impl Fn(Provider, SecretKey, ContractId) -> T) -> (T, T) {
+        (
+            self.load(self.owner_key(), &load),
+            self.load(self.claimant_key(), &load),
+        )
+    }
+
+    /// The make-claim script for claims against the owner, run by the claimant.
+    pub fn script(&self) -> MakeClaim<WalletUnlocked> {
+        let configurables = MakeClaimConfigurables::default()
+            .with_CLAIMS_CONTRACT_ADDRESS(Bits256(*self.contract_id()))
+            .unwrap()
+            .with_OWNER(self.owner().address().into())
+            .unwrap();
+
+        MakeClaim::new(self.claimant().clone(), MAKE_CLAIM_SCRIPT_BINARY)
+            .with_configurables(configurables)
+    }
+
+    /// The owner's claimable predicate, spendable by [`Harness::script`].
+    pub async fn predicate(&self) -> Predicate {
+        let configurables = ClaimableConfigurables::default()
+            .with_MAKE_CLAIM_SCRIPT_HASH(get_script_bytecode_hash(&self.script()).await)
+            .unwrap()
+            .with_OWNER(self.owner().address().into())
+            .unwrap();
+
+        Predicate::load_from(CLAIMABLE_PREDICATE_BINARY)
+            .unwrap()
+            .with_provider(self.provider.clone())
+            .with_configurables(configurables)
+    }
+
+    pub fn client(&self) -> FuelClient {
+        FuelClient::new(self.provider.url()).unwrap()
+    }
+
+    pub async fn last_tx_fee(&self) -> u64 {
+        get_last_tx_fee(&self.client()).await
+    }
+
+    pub async fn produce_blocks(&self, blocks: u32) -> u32 {
+        produce_blocks(&self.provider, blocks).await
+    }
+
+    /// Produce blocks until a claim filed at `claim_height` can be fulfilled by a
+    /// transaction in the next block.
+    pub async fn produce_blocks_until_fulfillable(&self, claim_height: u32) -> u32 {
+        produce_blocks_until(&self.provider, claim_height + CHALLENGE_PERIOD - 1).await
+    }
+}
+
+/// The hash the predicate expects of make-claim transactions.
+pub async fn get_script_bytecode_hash(script: &MakeClaim<WalletUnlocked>) -> Bits256 {
+    use sha2::Digest;
+    use sha2::Sha256;
+
+    let tx = script
+        .main(Address::zeroed(), 0, 0, Bits256::zeroed())
+        .build_tx()
+        .await
+        .unwrap();
+
+    let mut hasher = Sha256::new();
+    hasher.update(tx.script());
+    Bits256(hasher.finalize().into())
+}
+
+/// Fee of the latest transaction, 0 if it didn't succeed.
+pub async fn get_last_tx_fee(client: &FuelClient) -> u64 {
+    let status = client
+        .transactions(PaginationRequest {
+            cursor: None,
+            results: 1,
+            direction: PageDirection::Backward,
+        })
+        .await
+        .unwrap()
+        .results[0]
+        .status
+        .clone();
+
+    if let TransactionStatus::Success { total_fee, .. } = status {
+        total_fee
+    } else {
+        0
+    }
+}
+
+/// Returns the new block height.
+pub async fn produce_blocks(provider: &Provider, blocks: u32) -> u32 {
+    provider
+        .produce_blocks(blocks, None)
+        .await
+        .expect("should be able to produce blocks")
+}
+
+/// Produce blocks until the chain is at least at `height`, and return the height.
+pub async fn produce_blocks_until(provider: &Provider, height: u32) -> u32 {
+    let current = provider.latest_block_height().await.unwrap();
+
+    match height.checked_sub(current) {
+        Some(blocks) if blocks > 0 => produce_blocks(provider, blocks).await,
+        _ => current,
+    }
+}